@@ -0,0 +1,12 @@
+//! Host build of the target independent modules in `../src`
+//!
+//! The firmware crate only builds for the ESP32-C6, so the modules under
+//! test are compiled here again for the host.
+
+#[path = "../../src"]
+mod firmware {
+    pub mod axs5106l;
+    pub mod clock;
+}
+
+pub use firmware::{axs5106l, clock};
@@ -0,0 +1,182 @@
+//! Scaling from raw panel values to display pixels and the orientations
+
+use embedded_hal_mock::eh1::i2c::{Mock, Transaction as I2cTransaction};
+use host_tests::axs5106l::{
+    AxisRange, Axs5106l, Axs5106lConfig, Register, Rotation, TouchInterrupt, Transaction,
+};
+use host_tests::clock::ManualClock;
+
+const TOUCH: u8 = 0x63;
+
+/// Display position of one finger at raw (`x`, `y`)
+fn display_point(
+    irq: &'static TouchInterrupt,
+    config: Axs5106lConfig,
+    x: u16,
+    y: u16,
+) -> (u16, u16) {
+    let frame = vec![
+        0,
+        1,
+        (x >> 8) as u8,
+        x as u8,
+        (y >> 8) as u8,
+        y as u8,
+        8,
+        4,
+        0,
+        0,
+        0,
+        0,
+        0,
+        0,
+    ];
+    let expected = [
+        I2cTransaction::write_read(TOUCH, vec![Register::ChipId.addr()], vec![0x51, 0x06, 0x00]),
+        I2cTransaction::write(TOUCH, vec![0xA4, 0x01]),
+        I2cTransaction::write_read(TOUCH, vec![Register::TouchData.addr()], frame),
+    ];
+    let mut mock = Mock::new(&expected);
+
+    let config = config.with_transaction(Transaction::WriteRead);
+    let mut touch =
+        Axs5106l::with_config(mock.clone(), irq, config).with_clock(ManualClock::new(0));
+    touch.init().unwrap();
+    touch.poll_touch().unwrap();
+    let point = touch.get_coordinates().unwrap().coords[0];
+
+    mock.done();
+    (point.x, point.y)
+}
+
+#[test]
+fn scale_rounds_to_the_nearest_pixel() {
+    let range = AxisRange::new(0, 4095);
+    // (raw, size, pixel)
+    let cases = [
+        (0, 172, 0),
+        (4095, 172, 171),
+        // 0.459 and 0.501 px
+        (11, 172, 0),
+        (12, 172, 1),
+        // 85.48 and 85.52 px
+        (2047, 172, 85),
+        (2048, 172, 86),
+        (0, 1, 0),
+        (4095, 1, 0),
+        (4095, 0, 0),
+    ];
+    for (raw, size, pixel) in cases {
+        assert_eq!(range.scale(raw, size), pixel, "raw {raw} to {size} px");
+    }
+
+    // Exactly half a pixel rounds up
+    assert_eq!(AxisRange::new(0, 2).scale(1, 2), 1);
+    assert_eq!(AxisRange::new(0, 9).scale(1, 4), 0);
+    assert_eq!(AxisRange::new(0, 9).scale(2, 4), 1);
+}
+
+#[test]
+fn scale_clamps_to_the_range() {
+    let range = AxisRange::new(100, 3900);
+    assert_eq!(range.scale(0, 320), 0);
+    assert_eq!(range.scale(100, 320), 0);
+    assert_eq!(range.scale(2000, 320), 160);
+    assert_eq!(range.scale(3900, 320), 319);
+    assert_eq!(range.scale(4095, 320), 319);
+
+    // An empty or inverted range maps everything to the first pixel
+    assert_eq!(AxisRange::new(500, 500).scale(500, 320), 0);
+    assert_eq!(AxisRange::new(600, 500).scale(550, 320), 0);
+}
+
+#[test]
+fn native_size_is_passed_through() {
+    let range = AxisRange::from_size(172);
+    for raw in 0..172 {
+        assert_eq!(range.scale(raw, 172), raw);
+    }
+}
+
+#[test]
+fn orientations_swap_and_mirror_axes() {
+    // (rotation, raw, display)
+    let cases = [
+        (Rotation::Rotate0, (10, 20), (161, 20)),
+        (Rotation::Rotate90, (10, 20), (20, 10)),
+        (Rotation::Rotate180, (10, 20), (10, 299)),
+        (Rotation::Rotate270, (10, 20), (309, 151)),
+        // Corners of the panel
+        (Rotation::Rotate0, (0, 0), (171, 0)),
+        (Rotation::Rotate0, (171, 319), (0, 319)),
+        (Rotation::Rotate90, (171, 319), (319, 171)),
+        (Rotation::Rotate180, (171, 319), (171, 0)),
+        (Rotation::Rotate270, (0, 0), (319, 171)),
+        (Rotation::Rotate270, (171, 319), (148, 0)),
+    ];
+    for (rotation, (x, y), display) in cases {
+        static IRQ: TouchInterrupt = TouchInterrupt::new();
+        let config = Axs5106lConfig::new(172, 320).with_orientation(rotation);
+        assert_eq!(
+            display_point(&IRQ, config, x, y),
+            display,
+            "{rotation:?} of ({x}, {y})"
+        );
+    }
+}
+
+#[test]
+fn orientation_is_applied_after_scaling() {
+    static IRQ: TouchInterrupt = TouchInterrupt::new();
+    let full = AxisRange::new(0, 4095);
+    // (rotation, raw, display)
+    let cases = [
+        (Rotation::Rotate0, (4095, 0), (0, 0)),
+        (Rotation::Rotate0, (2048, 4095), (85, 319)),
+        (Rotation::Rotate90, (2048, 4095), (319, 86)),
+        (Rotation::Rotate180, (2048, 4095), (86, 0)),
+        (Rotation::Rotate270, (2048, 4095), (233, 0)),
+    ];
+    for (rotation, (x, y), display) in cases {
+        let config = Axs5106lConfig::new(172, 320)
+            .with_native_range(full, full)
+            .with_orientation(rotation);
+        assert_eq!(
+            display_point(&IRQ, config, x, y),
+            display,
+            "{rotation:?} of ({x}, {y})"
+        );
+    }
+}
+
+#[test]
+fn display_resolution_keeps_the_native_ranges() {
+    static IRQ: TouchInterrupt = TouchInterrupt::new();
+    let config = Axs5106lConfig::new(172, 320).with_display_resolution(86, 160);
+    assert_eq!(config.display_resolution(), (86, 160));
+    assert_eq!(
+        config.native_range(),
+        (AxisRange::new(0, 171), AxisRange::new(0, 319))
+    );
+
+    // Still scaled from the controller's 172 x 320
+    assert_eq!(display_point(&IRQ, config, 0, 319), (85, 159));
+    assert_eq!(display_point(&IRQ, config, 171, 0), (0, 0));
+    assert_eq!(display_point(&IRQ, config, 100, 200), (35, 100));
+}
+
+#[test]
+fn native_resolution_is_set_separately() {
+    static IRQ: TouchInterrupt = TouchInterrupt::new();
+    let config = Axs5106lConfig::new(172, 320)
+        .with_display_resolution(86, 160)
+        .with_native_resolution(86, 160);
+    assert_eq!(
+        config.native_range(),
+        (AxisRange::new(0, 85), AxisRange::new(0, 159))
+    );
+
+    // Passed through without scaling
+    assert_eq!(display_point(&IRQ, config, 0, 159), (85, 159));
+    assert_eq!(display_point(&IRQ, config, 50, 100), (35, 100));
+}
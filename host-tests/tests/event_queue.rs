@@ -0,0 +1,127 @@
+//! Touch event queue shared between the interrupt handler and the UI loop
+
+use host_tests::axs5106l::{TouchEvent, TouchEventQueue};
+
+fn down(id: u8, x: u16) -> TouchEvent {
+    TouchEvent::Down {
+        id,
+        x,
+        y: 0,
+        timestamp_us: 0,
+    }
+}
+
+fn moved(id: u8, x: u16) -> TouchEvent {
+    TouchEvent::Move {
+        id,
+        x,
+        y: 0,
+        timestamp_us: 0,
+    }
+}
+
+fn up(id: u8, x: u16) -> TouchEvent {
+    TouchEvent::Up {
+        id,
+        x,
+        y: 0,
+        timestamp_us: 0,
+    }
+}
+
+fn drain<const N: usize>(queue: &TouchEventQueue<N>) -> Vec<TouchEvent> {
+    core::iter::from_fn(|| queue.pop()).collect()
+}
+
+#[test]
+fn moves_of_one_finger_are_merged() {
+    let queue = TouchEventQueue::<8>::new();
+    queue.push(down(0, 1));
+    queue.push(moved(0, 2));
+    queue.push(moved(0, 3));
+    queue.push(moved(0, 4));
+
+    assert_eq!(drain(&queue), [down(0, 1), moved(0, 4)]);
+}
+
+#[test]
+fn moves_are_not_merged_across_fingers_or_other_events() {
+    let queue = TouchEventQueue::<8>::new();
+    queue.push(moved(0, 1));
+    queue.push(moved(1, 2));
+    queue.push(moved(0, 3));
+    queue.push(up(0, 3));
+    queue.push(moved(1, 4));
+    queue.push(down(0, 5));
+    queue.push(moved(0, 6));
+
+    assert_eq!(
+        drain(&queue),
+        [
+            moved(0, 1),
+            moved(1, 2),
+            moved(0, 3),
+            up(0, 3),
+            moved(1, 4),
+            down(0, 5),
+            moved(0, 6),
+        ]
+    );
+}
+
+#[test]
+fn full_queue_drops_and_counts() {
+    let queue = TouchEventQueue::<2>::new();
+    assert!(queue.push(down(0, 1)));
+    assert!(queue.push(down(1, 2)));
+    assert!(!queue.push(down(2, 3)));
+    assert!(!queue.push(up(0, 1)));
+    assert_eq!(queue.overflow_count(), 2);
+    assert_eq!(queue.len(), 2);
+
+    // A move is still merged into a move at the tail of a full queue
+    let queue = TouchEventQueue::<2>::new();
+    queue.push(down(0, 1));
+    queue.push(moved(0, 2));
+    assert!(queue.push(moved(0, 3)));
+    assert_eq!(queue.overflow_count(), 0);
+    assert_eq!(drain(&queue), [down(0, 1), moved(0, 3)]);
+}
+
+#[test]
+fn head_wraps_around() {
+    let queue = TouchEventQueue::<3>::new();
+    for round in 0..5u16 {
+        queue.push(down(0, round));
+        queue.push(up(0, round));
+        assert_eq!(queue.pop(), Some(down(0, round)));
+        queue.push(moved(1, round));
+        assert_eq!(drain(&queue), [up(0, round), moved(1, round)]);
+        assert!(queue.is_empty());
+    }
+    assert_eq!(queue.overflow_count(), 0);
+}
+
+#[test]
+fn truncate_drops_only_newer_events() {
+    let queue = TouchEventQueue::<4>::new();
+    // Move the head so the kept and dropped events wrap around
+    queue.push(down(9, 0));
+    queue.push(up(9, 0));
+    queue.pop();
+    queue.pop();
+
+    queue.push(down(0, 1));
+    queue.push(moved(0, 2));
+    let queued = queue.len();
+    queue.push(down(1, 3));
+    queue.push(moved(1, 4));
+    queue.truncate(queued);
+
+    assert_eq!(drain(&queue), [down(0, 1), moved(0, 2)]);
+
+    // Longer than the queue keeps everything
+    queue.push(down(2, 5));
+    queue.truncate(10);
+    assert_eq!(drain(&queue), [down(2, 5)]);
+}
@@ -0,0 +1,162 @@
+//! Finger velocity and acceleration from frames read at scripted times
+//!
+//! Frames are read with `poll_touch` while a `ManualClock` is moved between
+//! reads, so every frame gets an exact timestamp.
+
+use embedded_hal_mock::eh1::i2c::{Mock, Transaction as I2cTransaction};
+use host_tests::axs5106l::{
+    Axs5106l, Axs5106lConfig, Motion, Register, TouchInterrupt, Transaction,
+};
+use host_tests::clock::ManualClock;
+
+const TOUCH: u8 = 0x63;
+
+/// Finger ID and raw position
+type Finger = (u8, u16, u16);
+
+/// A touch frame with one finger at raw (`x`, `y`), or none
+fn frame(finger: Option<Finger>) -> Vec<u8> {
+    let mut data = vec![0u8; 14];
+    if let Some((id, x, y)) = finger {
+        data[1] = 1;
+        data[2..6].copy_from_slice(&[(x >> 8) as u8, x as u8, (id << 4) | (y >> 8) as u8, y as u8]);
+    }
+    data
+}
+
+/// Expected bus traffic of `init()` followed by one read per frame
+fn expected(frames: &[Option<Finger>]) -> Vec<I2cTransaction> {
+    let mut expected = vec![
+        I2cTransaction::write_read(TOUCH, vec![Register::ChipId.addr()], vec![0x51, 0x06, 0x00]),
+        I2cTransaction::write(TOUCH, vec![0xA4, 0x01]),
+    ];
+    expected.extend(frames.iter().map(|&finger| {
+        I2cTransaction::write_read(TOUCH, vec![Register::TouchData.addr()], frame(finger))
+    }));
+    expected
+}
+
+/// Read `frames` at the given times and return the motion of finger 0
+/// after each of them
+fn run(irq: &'static TouchInterrupt, frames: &[(u64, Option<Finger>)]) -> Vec<Motion> {
+    let fingers: Vec<_> = frames.iter().map(|&(_, finger)| finger).collect();
+    let mut mock = Mock::new(&expected(&fingers));
+
+    // Rotate0 mirrors X, Y goes straight through
+    let config = Axs5106lConfig::new(172, 320).with_transaction(Transaction::WriteRead);
+    let clock = ManualClock::new(0);
+    let mut touch = Axs5106l::with_config(mock.clone(), irq, config).with_clock(&clock);
+    touch.init().unwrap();
+
+    let motions = frames
+        .iter()
+        .map(|&(time_us, _)| {
+            clock.set(time_us);
+            touch.poll_touch().unwrap();
+            touch.motion(0).unwrap_or_default()
+        })
+        .collect();
+
+    mock.done();
+    motions
+}
+
+fn assert_motion(motion: Motion, vx: f32, vy: f32, ax: f32, ay: f32) {
+    let close = |a: f32, b: f32| (a - b).abs() <= 0.01 * b.abs().max(1.0);
+    assert!(
+        close(motion.vx, vx)
+            && close(motion.vy, vy)
+            && close(motion.ax, ax)
+            && close(motion.ay, ay),
+        "{motion:?}, expected vx {vx} vy {vy} ax {ax} ay {ay}"
+    );
+}
+
+#[test]
+fn first_sample_has_no_motion() {
+    static IRQ: TouchInterrupt = TouchInterrupt::new();
+    let motions = run(&IRQ, &[(5_000, Some((0, 50, 100)))]);
+    assert_motion(motions[0], 0.0, 0.0, 0.0, 0.0);
+}
+
+#[test]
+fn velocity_is_smoothed_and_acceleration_follows_it() {
+    static IRQ: TouchInterrupt = TouchInterrupt::new();
+    // 10 px per 10 ms is 1000 px/s on both axes, X mirrored by Rotate0
+    let motions = run(
+        &IRQ,
+        &[
+            (0, Some((0, 50, 100))),
+            (10_000, Some((0, 40, 110))),
+            (20_000, Some((0, 30, 120))),
+            (30_000, Some((0, 30, 120))),
+        ],
+    );
+
+    assert_motion(motions[0], 0.0, 0.0, 0.0, 0.0);
+    // Half of the new velocity is taken per sample
+    assert_motion(motions[1], 500.0, 500.0, 50_000.0, 50_000.0);
+    assert_motion(motions[2], 750.0, 750.0, 25_000.0, 25_000.0);
+    // Stopping slows down by half as well
+    assert_motion(motions[3], 375.0, 375.0, -37_500.0, -37_500.0);
+}
+
+#[test]
+fn zero_time_delta_keeps_the_previous_sample() {
+    static IRQ: TouchInterrupt = TouchInterrupt::new();
+    let motions = run(
+        &IRQ,
+        &[
+            (0, Some((0, 50, 100))),
+            // Same timestamp, the position is not taken either
+            (0, Some((0, 50, 105))),
+            (10_000, Some((0, 50, 110))),
+            (10_000, Some((0, 50, 200))),
+        ],
+    );
+
+    assert_motion(motions[0], 0.0, 0.0, 0.0, 0.0);
+    assert_motion(motions[1], 0.0, 0.0, 0.0, 0.0);
+    // Measured from the first frame: 10 px in 10 ms
+    assert_motion(motions[2], 0.0, 500.0, 0.0, 50_000.0);
+    assert_motion(motions[3], 0.0, 500.0, 0.0, 50_000.0);
+    assert!(motions[3].vy.is_finite() && motions[3].ay.is_finite());
+}
+
+#[test]
+fn long_gap_restarts_the_estimate() {
+    static IRQ: TouchInterrupt = TouchInterrupt::new();
+    let motions = run(
+        &IRQ,
+        &[
+            (0, Some((0, 50, 100))),
+            (10_000, Some((0, 50, 110))),
+            (210_000, Some((0, 50, 300))),
+            (220_000, Some((0, 50, 310))),
+        ],
+    );
+
+    assert_motion(motions[1], 0.0, 500.0, 0.0, 50_000.0);
+    assert_motion(motions[2], 0.0, 0.0, 0.0, 0.0);
+    assert_motion(motions[3], 0.0, 500.0, 0.0, 50_000.0);
+}
+
+#[test]
+fn motion_is_kept_after_release() {
+    static IRQ: TouchInterrupt = TouchInterrupt::new();
+    let motions = run(
+        &IRQ,
+        &[
+            (0, Some((0, 50, 100))),
+            (10_000, Some((0, 50, 120))),
+            (20_000, None),
+            // The same ID going down again starts from rest
+            (30_000, Some((0, 50, 200))),
+        ],
+    );
+
+    assert_motion(motions[1], 0.0, 1000.0, 0.0, 100_000.0);
+    assert_motion(motions[2], 0.0, 1000.0, 0.0, 100_000.0);
+    assert_motion(motions[3], 0.0, 0.0, 0.0, 0.0);
+    assert!((motions[2].speed() - 1000.0).abs() < 1.0);
+}
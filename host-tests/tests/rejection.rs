@@ -0,0 +1,387 @@
+//! Palm rejection and the noise filter, one scripted frame sequence per case
+//!
+//! Every frame is read with `poll_touch`, and the points left after
+//! rejection and filtering are compared in display coordinates.
+
+use embedded_hal_mock::eh1::i2c::{Mock, Transaction as I2cTransaction};
+use host_tests::axs5106l::{
+    Axs5106l, Axs5106lConfig, Filter, Register, RejectionPolicy, Rotation, TouchInterrupt,
+    Transaction,
+};
+use host_tests::clock::ManualClock;
+
+const TOUCH: u8 = 0x63;
+
+/// Finger ID and display position of a reported point
+type Reported = (u8, u16, u16);
+
+/// A point in a frame as reported by the controller
+#[derive(Clone, Copy)]
+struct Point {
+    id: u8,
+    x: u16,
+    y: u16,
+    weight: u8,
+    area: u8,
+}
+
+/// A finger of normal size
+const fn finger(id: u8, x: u16, y: u16) -> Point {
+    Point {
+        id,
+        x,
+        y,
+        weight: 20,
+        area: 10,
+    }
+}
+
+/// A contact of the given size
+const fn contact(id: u8, weight: u8, area: u8) -> Point {
+    Point {
+        id,
+        x: 50,
+        y: 50,
+        weight,
+        area,
+    }
+}
+
+/// A touch frame, `reported` is the point count in its header
+struct Frame {
+    reported: u8,
+    points: &'static [Point],
+}
+
+const fn frame(points: &'static [Point]) -> Frame {
+    Frame {
+        reported: points.len() as u8,
+        points,
+    }
+}
+
+const EMPTY: Frame = frame(&[]);
+
+fn encode(frame: &Frame) -> Vec<u8> {
+    let mut data = vec![0u8; 14];
+    data[1] = frame.reported;
+    for (i, p) in frame.points.iter().enumerate() {
+        let base = 2 + i * 6;
+        data[base..base + 6].copy_from_slice(&[
+            (p.x >> 8) as u8,
+            p.x as u8,
+            (p.id << 4) | (p.y >> 8) as u8,
+            p.y as u8,
+            p.weight,
+            p.area,
+        ]);
+    }
+    data
+}
+
+/// Read `frames` and return the (id, x, y) points reported after each of
+/// them, together with the driver's rejected frame count
+fn run(
+    irq: &'static TouchInterrupt,
+    config: Axs5106lConfig,
+    frames: &[Frame],
+) -> (Vec<Vec<Reported>>, u32) {
+    let mut expected = vec![
+        I2cTransaction::write_read(TOUCH, vec![Register::ChipId.addr()], vec![0x51, 0x06, 0x00]),
+        I2cTransaction::write(TOUCH, vec![0xA4, 0x01]),
+    ];
+    expected.extend(frames.iter().map(|frame| {
+        I2cTransaction::write_read(TOUCH, vec![Register::TouchData.addr()], encode(frame))
+    }));
+    let mut mock = Mock::new(&expected);
+
+    // Rotate90 only swaps the axes, so raw values come out unchanged
+    let config = config
+        .with_transaction(Transaction::WriteRead)
+        .with_orientation(Rotation::Rotate90);
+    let mut touch =
+        Axs5106l::with_config(mock.clone(), irq, config).with_clock(ManualClock::new(0));
+    touch.init().unwrap();
+
+    let reported = frames
+        .iter()
+        .map(|_| {
+            touch.poll_touch().unwrap();
+            let count = touch.touch_count() as usize;
+            touch.get_coordinates().map_or(Vec::new(), |data| {
+                data.coords[..count]
+                    .iter()
+                    .map(|p| (p.id, p.y, p.x))
+                    .collect()
+            })
+        })
+        .collect();
+
+    mock.done();
+    (reported, touch.rejected_frames())
+}
+
+struct RejectionCase {
+    name: &'static str,
+    policy: RejectionPolicy,
+    frames: &'static [Frame],
+    /// Finger IDs kept in every frame
+    kept: &'static [&'static [u8]],
+    rejected_frames: u32,
+}
+
+/// The driver's defaults, every check disabled
+const NO_LIMITS: RejectionPolicy = RejectionPolicy {
+    max_area: 0,
+    max_weight: 0,
+    max_points: 0,
+    min_frames: 1,
+};
+
+const PALM: RejectionPolicy = RejectionPolicy {
+    max_area: 40,
+    max_weight: 100,
+    max_points: 0,
+    min_frames: 1,
+};
+
+#[test]
+fn rejection() {
+    const CASES: &[RejectionCase] = &[
+        RejectionCase {
+            name: "fingers within the limits",
+            policy: PALM,
+            frames: &[
+                frame(&[contact(0, 100, 40)]),
+                frame(&[contact(0, 20, 10), contact(1, 30, 20)]),
+            ],
+            kept: &[&[0], &[0, 1]],
+            rejected_frames: 0,
+        },
+        RejectionCase {
+            name: "large area",
+            policy: PALM,
+            frames: &[frame(&[contact(0, 20, 41)])],
+            kept: &[&[]],
+            rejected_frames: 1,
+        },
+        RejectionCase {
+            name: "high weight",
+            policy: PALM,
+            frames: &[frame(&[contact(0, 101, 10)])],
+            kept: &[&[]],
+            rejected_frames: 1,
+        },
+        RejectionCase {
+            name: "one oversized point drops the whole frame",
+            policy: PALM,
+            frames: &[frame(&[contact(0, 20, 10), contact(1, 20, 200)])],
+            kept: &[&[]],
+            rejected_frames: 1,
+        },
+        RejectionCase {
+            name: "palm stays rejected until the panel is clear",
+            policy: PALM,
+            frames: &[
+                frame(&[contact(0, 20, 200)]),
+                frame(&[contact(0, 20, 10)]),
+                frame(&[contact(1, 20, 10)]),
+                EMPTY,
+                frame(&[contact(0, 20, 10)]),
+            ],
+            kept: &[&[], &[], &[], &[], &[0]],
+            rejected_frames: 3,
+        },
+        RejectionCase {
+            name: "limits of zero are disabled",
+            policy: NO_LIMITS,
+            frames: &[frame(&[contact(0, 255, 255), contact(1, 255, 255)])],
+            kept: &[&[0, 1]],
+            rejected_frames: 0,
+        },
+        RejectionCase {
+            name: "too many points",
+            policy: RejectionPolicy {
+                max_points: 1,
+                ..NO_LIMITS
+            },
+            frames: &[
+                frame(&[finger(0, 10, 10)]),
+                frame(&[finger(0, 10, 10), finger(1, 90, 90)]),
+            ],
+            kept: &[&[0], &[]],
+            rejected_frames: 1,
+        },
+        RejectionCase {
+            name: "header count beyond the frame is checked against the limit",
+            policy: RejectionPolicy {
+                max_points: 2,
+                ..NO_LIMITS
+            },
+            frames: &[Frame {
+                reported: 5,
+                points: &[finger(0, 10, 10), finger(1, 90, 90)],
+            }],
+            kept: &[&[]],
+            rejected_frames: 1,
+        },
+        RejectionCase {
+            name: "header count beyond the frame only reads the points it holds",
+            policy: NO_LIMITS,
+            frames: &[Frame {
+                reported: 15,
+                points: &[finger(0, 10, 10), finger(1, 90, 90)],
+            }],
+            kept: &[&[0, 1]],
+            rejected_frames: 0,
+        },
+        RejectionCase {
+            name: "new fingers wait for confirmation",
+            policy: RejectionPolicy {
+                min_frames: 2,
+                ..NO_LIMITS
+            },
+            frames: &[
+                frame(&[finger(0, 10, 10)]),
+                frame(&[finger(0, 10, 10), finger(1, 90, 90)]),
+                frame(&[finger(0, 10, 10), finger(1, 90, 90)]),
+                frame(&[finger(1, 90, 90)]),
+                frame(&[finger(0, 10, 10), finger(1, 90, 90)]),
+            ],
+            kept: &[&[], &[0], &[0, 1], &[1], &[1]],
+            rejected_frames: 0,
+        },
+    ];
+
+    assert_eq!(NO_LIMITS, RejectionPolicy::default());
+    for case in CASES {
+        static IRQ: TouchInterrupt = TouchInterrupt::new();
+        let config = Axs5106lConfig::new(172, 320).with_rejection(case.policy);
+        let (reported, rejected_frames) = run(&IRQ, config, case.frames);
+        let kept: Vec<Vec<u8>> = reported
+            .iter()
+            .map(|points| points.iter().map(|&(id, _, _)| id).collect())
+            .collect();
+        assert_eq!(kept, case.kept, "{}", case.name);
+        assert_eq!(rejected_frames, case.rejected_frames, "{}", case.name);
+    }
+}
+
+struct FilterCase {
+    name: &'static str,
+    filter: Filter,
+    frames: &'static [Frame],
+    /// (id, x, y) points reported after every frame
+    reported: &'static [&'static [Reported]],
+}
+
+#[test]
+fn filter() {
+    const CASES: &[FilterCase] = &[
+        FilterCase {
+            name: "disabled",
+            filter: Filter {
+                deadband: 0,
+                smoothing: 0,
+            },
+            frames: &[frame(&[finger(0, 100, 100)]), frame(&[finger(0, 101, 99)])],
+            reported: &[&[(0, 100, 100)], &[(0, 101, 99)]],
+        },
+        FilterCase {
+            name: "deadband holds small movements",
+            filter: Filter {
+                deadband: 5,
+                smoothing: 0,
+            },
+            frames: &[
+                frame(&[finger(0, 100, 100)]),
+                frame(&[finger(0, 104, 96)]),
+                frame(&[finger(0, 105, 100)]),
+            ],
+            reported: &[&[(0, 100, 100)], &[(0, 100, 100)], &[(0, 105, 100)]],
+        },
+        FilterCase {
+            name: "smoothing moves part of the way",
+            filter: Filter {
+                deadband: 0,
+                smoothing: 50,
+            },
+            frames: &[
+                frame(&[finger(0, 100, 100)]),
+                frame(&[finger(0, 120, 80)]),
+                frame(&[finger(0, 120, 80)]),
+            ],
+            reported: &[&[(0, 100, 100)], &[(0, 110, 90)], &[(0, 115, 85)]],
+        },
+        FilterCase {
+            name: "fingers are filtered separately",
+            filter: Filter {
+                deadband: 0,
+                smoothing: 50,
+            },
+            frames: &[
+                frame(&[finger(0, 100, 100), finger(1, 50, 50)]),
+                frame(&[finger(1, 70, 70), finger(0, 120, 100)]),
+            ],
+            reported: &[&[(0, 100, 100), (1, 50, 50)], &[(1, 60, 60), (0, 110, 100)]],
+        },
+        FilterCase {
+            name: "a new finger starts unfiltered",
+            filter: Filter {
+                deadband: 0,
+                smoothing: 50,
+            },
+            frames: &[frame(&[finger(0, 100, 100)]), frame(&[finger(1, 140, 140)])],
+            reported: &[&[(0, 100, 100)], &[(1, 140, 140)]],
+        },
+        FilterCase {
+            name: "lifting the finger resets the smoothing",
+            filter: Filter {
+                deadband: 0,
+                smoothing: 50,
+            },
+            frames: &[
+                frame(&[finger(0, 100, 100)]),
+                EMPTY,
+                frame(&[finger(0, 140, 140)]),
+            ],
+            reported: &[&[(0, 100, 100)], &[], &[(0, 140, 140)]],
+        },
+        FilterCase {
+            name: "lifting the finger resets the deadband",
+            filter: Filter {
+                deadband: 5,
+                smoothing: 0,
+            },
+            frames: &[
+                frame(&[finger(0, 100, 100)]),
+                EMPTY,
+                frame(&[finger(0, 103, 103)]),
+            ],
+            reported: &[&[(0, 100, 100)], &[], &[(0, 103, 103)]],
+        },
+        FilterCase {
+            name: "a rejected frame resets the filter",
+            filter: Filter {
+                deadband: 5,
+                smoothing: 0,
+            },
+            frames: &[
+                frame(&[finger(0, 100, 100)]),
+                frame(&[finger(0, 101, 101), contact(1, 20, 200)]),
+                EMPTY,
+                frame(&[finger(0, 103, 103)]),
+            ],
+            reported: &[&[(0, 100, 100)], &[], &[], &[(0, 103, 103)]],
+        },
+    ];
+
+    for case in CASES {
+        static IRQ: TouchInterrupt = TouchInterrupt::new();
+        let config = Axs5106lConfig::new(172, 320)
+            .with_filter(case.filter)
+            .with_rejection(PALM);
+        let (reported, _) = run(&IRQ, config, case.frames);
+        assert_eq!(reported, case.reported, "{}", case.name);
+    }
+}
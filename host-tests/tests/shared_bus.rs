@@ -0,0 +1,135 @@
+//! AXS5106L on an I2C bus shared through `embedded-hal-bus`
+//!
+//! The mock checks the exact order of bus transactions, with another device
+//! at `SENSOR` using the same bus between driver calls.
+
+use core::cell::RefCell;
+
+use critical_section::Mutex;
+use embedded_hal::i2c::I2c;
+use embedded_hal_bus::i2c::{CriticalSectionDevice, RefCellDevice};
+use embedded_hal_mock::eh1::i2c::{Mock, Transaction as I2cTransaction};
+use host_tests::axs5106l::{Axs5106l, Axs5106lConfig, Register, TouchInterrupt, Transaction};
+use host_tests::clock::ManualClock;
+
+const TOUCH: u8 = 0x63;
+const SENSOR: u8 = 0x44;
+
+/// Chip ID read and interrupt mode write of `init()`
+fn init(transaction: Transaction) -> Vec<I2cTransaction> {
+    let mut expected = read(transaction, Register::ChipId.addr(), vec![0x51, 0x06, 0x00]);
+    expected.push(I2cTransaction::write(TOUCH, vec![0xA4, 0x01]));
+    expected
+}
+
+/// A register read of the touch controller
+fn read(transaction: Transaction, reg: u8, response: Vec<u8>) -> Vec<I2cTransaction> {
+    match transaction {
+        Transaction::Separate => vec![
+            I2cTransaction::write(TOUCH, vec![reg]),
+            I2cTransaction::read(TOUCH, response),
+        ],
+        Transaction::WriteRead => vec![I2cTransaction::write_read(TOUCH, vec![reg], response)],
+    }
+}
+
+/// A measurement of the other device on the bus
+fn sensor() -> Vec<I2cTransaction> {
+    vec![
+        I2cTransaction::write(SENSOR, vec![0x24, 0x00]),
+        I2cTransaction::read(SENSOR, vec![0x66, 0x8A]),
+    ]
+}
+
+fn measure<I: I2c>(sensor: &mut I) {
+    let mut data = [0u8; 2];
+    sensor.write(SENSOR, &[0x24, 0x00]).unwrap();
+    sensor.read(SENSOR, &mut data).unwrap();
+    assert_eq!(data, [0x66, 0x8A]);
+}
+
+/// One finger at raw (10, 20)
+fn frame() -> Vec<u8> {
+    vec![0, 1, 0x00, 10, 0x00, 20, 8, 4, 0, 0, 0, 0, 0, 0]
+}
+
+#[test]
+fn separate_transactions_through_refcell_device() {
+    static IRQ: TouchInterrupt = TouchInterrupt::new();
+    let transaction = Transaction::Separate;
+    let expected = [
+        init(transaction),
+        sensor(),
+        read(transaction, Register::TouchData.addr(), frame()),
+        sensor(),
+        read(transaction, Register::Threshold.addr(), vec![60]),
+    ]
+    .concat();
+    let mut mock = Mock::new(&expected);
+
+    let bus = RefCell::new(mock.clone());
+    let config = Axs5106lConfig::new(172, 320).with_transaction(transaction);
+    let mut touch = Axs5106l::with_config(RefCellDevice::new(&bus), &IRQ, config)
+        .with_clock(ManualClock::new(0));
+    let mut other = RefCellDevice::new(&bus);
+
+    touch.init().unwrap();
+    measure(&mut other);
+    IRQ.signal(1_000);
+    touch.read_touch().unwrap();
+    assert_eq!(touch.touch_count(), 1);
+    measure(&mut other);
+    assert_eq!(touch.read_register(Register::Threshold).unwrap(), 60);
+
+    mock.done();
+}
+
+#[test]
+fn separate_transactions_through_critical_section_device() {
+    static IRQ: TouchInterrupt = TouchInterrupt::new();
+    let transaction = Transaction::Separate;
+    let expected = [
+        init(transaction),
+        sensor(),
+        read(transaction, Register::Threshold.addr(), vec![60]),
+    ]
+    .concat();
+    let mut mock = Mock::new(&expected);
+
+    let bus = Mutex::new(RefCell::new(mock.clone()));
+    let config = Axs5106lConfig::new(172, 320).with_transaction(transaction);
+    let mut touch = Axs5106l::with_config(CriticalSectionDevice::new(&bus), &IRQ, config)
+        .with_clock(ManualClock::new(0));
+    let mut other = CriticalSectionDevice::new(&bus);
+
+    touch.init().unwrap();
+    measure(&mut other);
+    assert_eq!(touch.read_register(Register::Threshold).unwrap(), 60);
+
+    mock.done();
+}
+
+#[test]
+fn write_read_keeps_the_bus() {
+    static IRQ: TouchInterrupt = TouchInterrupt::new();
+    let transaction = Transaction::WriteRead;
+    let expected = [
+        init(transaction),
+        sensor(),
+        read(transaction, Register::Threshold.addr(), vec![60]),
+    ]
+    .concat();
+    let mut mock = Mock::new(&expected);
+
+    let bus = RefCell::new(mock.clone());
+    let config = Axs5106lConfig::new(172, 320).with_transaction(transaction);
+    let mut touch = Axs5106l::with_config(RefCellDevice::new(&bus), &IRQ, config)
+        .with_clock(ManualClock::new(0));
+    let mut other = RefCellDevice::new(&bus);
+
+    touch.init().unwrap();
+    measure(&mut other);
+    assert_eq!(touch.read_register(Register::Threshold).unwrap(), 60);
+
+    mock.done();
+}
@@ -1,5 +1,7 @@
 #![allow(dead_code)]
 
+use embedded_hal::delay::DelayNs;
+use embedded_hal::digital::OutputPin;
 use embedded_hal::i2c::I2c;
 
 // Import standard library traits for derive
@@ -12,25 +14,68 @@ use core::marker::Copy;
 use core::option::Option;
 use core::prelude::rust_2021::derive;
 use core::result::Result;
-use esp_println::println;
+
+use crate::clock::{Clock, SystemClock};
+
+mod config;
+mod events;
+mod filter;
+#[cfg(feature = "unverified-fw-download")]
+mod firmware;
+mod interrupt;
+mod motion;
+mod recovery;
+mod registers;
+mod rejection;
+
+pub use config::{AxisRange, Axs5106lConfig, Calibration, Filter, Transaction, VirtualKey};
+pub use events::{TouchEvent, TouchEventQueue};
+use filter::FilterState;
+#[cfg(feature = "unverified-fw-download")]
+pub use firmware::{FirmwareError, FirmwarePhase, FirmwareProgress, FirmwareUpdate, image_version};
+pub use interrupt::TouchInterrupt;
+pub use motion::Motion;
+use motion::MotionTracker;
+pub use recovery::{RecoveryEvent, RecoveryPolicy};
+pub use registers::{
+    FromRegister, GestureEnable, InterruptMode, PowerMode, Register, RegisterValue, ReportRate,
+    Threshold, WakeGesture, WakeGestures, WritableRegister,
+};
+pub use rejection::RejectionPolicy;
+use rejection::RejectionState;
 
 /// Maximum number of touch points supported
 const MAX_TOUCH_POINTS: usize = 5;
 
+/// Length of a touch frame read from the touch data register
+const TOUCH_FRAME_LEN: usize = 14;
+
+/// Number of touch points that fit into a touch frame
+const MAX_FRAME_POINTS: usize = (TOUCH_FRAME_LEN - 2) / 6;
+
 /// I2C address of the AXS5106L touch controller
 const AXS5106L_ADDR: u8 = 0x63;
 
-/// Register address for device ID
-const AXS5106L_ID_REG: u8 = 0x08;
+/// Maximum number of data bytes in a single register write
+const I2C_WRITE_MAX: usize = 32;
+
+/// Time the reset line is held low when waking from sleep (ms)
+const RESET_LOW_MS: u32 = 200;
 
-/// Register address for touch data
-const AXS5106L_TOUCH_DATA_REG: u8 = 0x01;
+/// Time to wait after releasing reset before talking to the controller (ms)
+const RESET_BOOT_MS: u32 = 200;
 
 /// Touch point coordinates
 #[derive(Clone, Copy, Default)]
 pub struct Coordinates {
     pub x: u16,
     pub y: u16,
+    /// Finger ID assigned by the controller, stable while the finger is down
+    pub id: u8,
+    /// Contact weight (pressure) reported by the controller
+    pub weight: u8,
+    /// Contact area reported by the controller
+    pub area: u8,
 }
 
 /// Touch data containing all touch points
@@ -38,10 +83,12 @@ pub struct Coordinates {
 pub struct TouchData {
     pub coords: [Coordinates; MAX_TOUCH_POINTS],
     pub touch_num: u8,
+    /// Time the frame was read, from the driver's clock (us)
+    pub timestamp_us: u64,
 }
 
 /// Display rotation modes
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub enum Rotation {
     Rotate0 = 0,
     Rotate90 = 1,
@@ -50,125 +97,484 @@ pub enum Rotation {
 }
 
 /// AXS5106L touch controller driver
-pub struct Axs5106l<I2C> {
+///
+/// Frames are timestamped with `C`, the system timer unless a different
+/// clock is set with [`Axs5106l::with_clock`].
+pub struct Axs5106l<I2C, C = SystemClock> {
     i2c: I2C,
-    width: u16,
-    height: u16,
-    rotation: Rotation,
+    clock: C,
+    motion: MotionTracker,
+    config: Axs5106lConfig,
+    filter: FilterState,
+    rejection: RejectionState,
     touch_data: TouchData,
-    touch_int_flag: bool,
+    frame_count: u32,
+    reported: TouchData,
+    pressed_keys: [Option<(u8, usize)>; MAX_TOUCH_POINTS],
+    interrupt: &'static TouchInterrupt,
+    last_interrupt_us: u64,
+    power_mode: PowerMode,
+    recovery: RecoveryPolicy,
+    consecutive_failures: u8,
+    recovery_attempts: u8,
+    recovery_count: u32,
+    error_count: u32,
 }
 
-impl<I2C, E> Axs5106l<I2C>
+impl<I2C, E> Axs5106l<I2C, SystemClock>
 where
     I2C: I2c<Error = E>,
 {
     /// Create a new AXS5106L driver instance
     ///
+    /// Uses the default configuration, see [`Axs5106lConfig::new`].
+    ///
     /// # Arguments
     /// * `i2c` - I2C bus instance
+    /// * `interrupt` - Interrupt cell signalled by the GPIO interrupt handler
     /// * `rotation` - Display rotation
     /// * `width` - Display width in pixels
     /// * `height` - Display height in pixels
-    pub fn new(i2c: I2C, rotation: Rotation, width: u16, height: u16) -> Self {
+    pub fn new(
+        i2c: I2C,
+        interrupt: &'static TouchInterrupt,
+        rotation: Rotation,
+        width: u16,
+        height: u16,
+    ) -> Self {
+        Self::with_config(
+            i2c,
+            interrupt,
+            Axs5106lConfig::new(width, height).with_orientation(rotation),
+        )
+    }
+
+    /// Create a new AXS5106L driver instance from a configuration
+    ///
+    /// # Arguments
+    /// * `i2c` - I2C bus instance
+    /// * `interrupt` - Interrupt cell signalled by the GPIO interrupt handler
+    /// * `config` - Bus, resolution, orientation, filter and calibration settings
+    ///
+    /// `i2c` can be a shared bus device from `embedded-hal-bus`, so other
+    /// devices stay usable on the same pins. `RefCellDevice` works when the
+    /// bus is only used from the main loop, `CriticalSectionDevice` when it
+    /// is also used from interrupt handlers. With [`Transaction::Separate`]
+    /// the bus is released between the register address write and the data
+    /// read:
+    ///
+    /// ```rust,ignore
+    /// use core::cell::RefCell;
+    /// use critical_section::Mutex;
+    /// use embedded_hal_bus::i2c::{CriticalSectionDevice, RefCellDevice};
+    ///
+    /// // Main loop only
+    /// let bus = RefCell::new(i2c);
+    /// let mut touch = Axs5106l::with_config(RefCellDevice::new(&bus), &TOUCH_IRQ, config);
+    /// let mut rtc = Rtc::new(RefCellDevice::new(&bus));
+    ///
+    /// // Also used from interrupt handlers
+    /// static BUS: StaticCell<Mutex<RefCell<I2c<'static, Blocking>>>> = StaticCell::new();
+    /// let bus = BUS.init(Mutex::new(RefCell::new(i2c)));
+    /// let mut touch = Axs5106l::with_config(CriticalSectionDevice::new(bus), &TOUCH_IRQ, config);
+    /// let mut sensor = Sensor::new(CriticalSectionDevice::new(bus));
+    /// ```
+    pub fn with_config(
+        i2c: I2C,
+        interrupt: &'static TouchInterrupt,
+        config: Axs5106lConfig,
+    ) -> Self {
         Self {
             i2c,
-            width,
-            height,
-            rotation,
+            clock: SystemClock,
+            motion: MotionTracker::default(),
+            config,
+            filter: FilterState::default(),
+            rejection: RejectionState::default(),
             touch_data: TouchData::default(),
-            touch_int_flag: false,
+            frame_count: 0,
+            reported: TouchData::default(),
+            pressed_keys: [None; MAX_TOUCH_POINTS],
+            interrupt,
+            last_interrupt_us: 0,
+            power_mode: PowerMode::Active,
+            recovery: RecoveryPolicy::default(),
+            consecutive_failures: 0,
+            recovery_attempts: 0,
+            recovery_count: 0,
+            error_count: 0,
         }
     }
+}
+
+impl<I2C, C> Axs5106l<I2C, C> {
+    /// Replace the clock used to timestamp touch frames
+    pub fn with_clock<C2: Clock>(self, clock: C2) -> Axs5106l<I2C, C2> {
+        Axs5106l {
+            i2c: self.i2c,
+            clock,
+            motion: self.motion,
+            config: self.config,
+            filter: self.filter,
+            rejection: self.rejection,
+            touch_data: self.touch_data,
+            frame_count: self.frame_count,
+            reported: self.reported,
+            pressed_keys: self.pressed_keys,
+            interrupt: self.interrupt,
+            last_interrupt_us: self.last_interrupt_us,
+            power_mode: self.power_mode,
+            recovery: self.recovery,
+            consecutive_failures: self.consecutive_failures,
+            recovery_attempts: self.recovery_attempts,
+            recovery_count: self.recovery_count,
+            error_count: self.error_count,
+        }
+    }
+}
+
+impl<I2C, E, C> Axs5106l<I2C, C>
+where
+    I2C: I2c<Error = E>,
+    C: Clock,
+{
+    /// Get the driver configuration
+    pub fn config(&self) -> &Axs5106lConfig {
+        &self.config
+    }
+
+    /// Destroy the driver and return the I2C bus
+    pub fn release(self) -> I2C {
+        self.i2c
+    }
 
     /// Initialize the touch controller
     ///
-    /// Reads the device ID register to verify communication
+    /// Reads the device ID register to verify communication and applies the
+    /// configured interrupt mode
     pub fn init(&mut self) -> Result<(), E> {
         let mut data = [0u8; 3];
-        self.i2c_read(AXS5106L_ID_REG, &mut data)?;
-        println!("init touch i2c");
-        // If data[0] is not zero, the device responded
-        if data[0] != 0 {
-            // Device ID read successfully
-        }
-        println!("TouchData: {},{},{}", data[0], data[1], data[2]);
+        self.i2c_read(Register::ChipId.addr(), &mut data)?;
 
-        Ok(())
+        // Lost on every reset, so it's written here rather than once
+        self.write_config(self.config.interrupt_mode)
     }
 
     /// Read from an I2C register
     ///
-    /// Uses two separate I2C transactions to match the working C++ implementation:
+    /// With [`Transaction::Separate`] (the default) two separate I2C
+    /// transactions are used to match the working C++ implementation:
     /// 1. Write the register address
     /// 2. Read the data
+    ///
+    /// With [`Transaction::WriteRead`] both happen in one transaction.
     fn i2c_read(&mut self, reg_addr: u8, data: &mut [u8]) -> Result<(), E> {
+        let addr = self.config.address;
+
+        if self.config.transaction == Transaction::WriteRead {
+            return self.i2c.write_read(addr, &[reg_addr], data);
+        }
+
         // First, write the register address
-        self.i2c.write(AXS5106L_ADDR, &[reg_addr])?;
+        self.i2c.write(addr, &[reg_addr])?;
 
         // Then, read the data in a separate transaction
-        self.i2c.read(AXS5106L_ADDR, data)
+        self.i2c.read(addr, data)
     }
 
     /// Write to an I2C register
-    #[allow(dead_code)]
+    ///
+    /// At most [`I2C_WRITE_MAX`] data bytes can be written at once.
     fn i2c_write(&mut self, reg_addr: u8, data: &[u8]) -> Result<(), E> {
-        let mut buffer = [0u8; 33]; // Max length: 1 (reg) + 32 (data)
+        debug_assert!(data.len() <= I2C_WRITE_MAX);
+        let mut buffer = [0u8; 1 + I2C_WRITE_MAX]; // Max length: 1 (reg) + 32 (data)
         buffer[0] = reg_addr;
         buffer[1..1 + data.len()].copy_from_slice(data);
-        self.i2c.write(AXS5106L_ADDR, &buffer[..1 + data.len()])
+        self.i2c
+            .write(self.config.address, &buffer[..1 + data.len()])
+    }
+
+    /// Read a single register
+    pub fn read_register(&mut self, reg: Register) -> Result<u8, E> {
+        let mut value = [0u8; 1];
+        self.i2c_read(reg.addr(), &mut value)?;
+        Ok(value[0])
+    }
+
+    /// Write a single register
+    ///
+    /// Only takes [`WritableRegister`]s, read-only registers such as
+    /// [`Register::TouchData`] can't be written.
+    pub fn write_register(&mut self, reg: WritableRegister, value: u8) -> Result<(), E> {
+        self.i2c_write(reg.addr(), &[value])
+    }
+
+    /// Read a register, change it with `f` and write it back
+    ///
+    /// Returns the value that was written.
+    pub fn modify_register<F>(&mut self, reg: WritableRegister, f: F) -> Result<u8, E>
+    where
+        F: FnOnce(u8) -> u8,
+    {
+        let value = f(self.read_register(reg.into())?);
+        self.write_register(reg, value)?;
+        Ok(value)
+    }
+
+    /// Read a typed configuration value
+    ///
+    /// Returns None if the register holds an undocumented value.
+    pub fn read_config<T: FromRegister>(&mut self) -> Result<Option<T>, E> {
+        Ok(T::from_bits(self.read_register(T::REGISTER)?))
+    }
+
+    /// Write a typed configuration value
+    pub fn write_config<T: RegisterValue>(&mut self, value: T) -> Result<(), E> {
+        self.write_register(T::WRITABLE, value.to_bits())
+    }
+
+    /// Read a typed configuration value, change it with `f` and write it back
+    ///
+    /// `f` receives None if the register holds an undocumented value.
+    /// Returns the value that was written.
+    pub fn modify_config<T, F>(&mut self, f: F) -> Result<T, E>
+    where
+        T: RegisterValue + Copy,
+        F: FnOnce(Option<T>) -> T,
+    {
+        let value = f(self.read_config::<T>()?);
+        self.write_config(value)?;
+        Ok(value)
+    }
+
+    /// Set the touch report rate in Hz
+    pub fn set_report_rate(&mut self, hz: u8) -> Result<(), E> {
+        self.write_config(ReportRate(hz))
+    }
+
+    /// Read the firmware version running on the controller
+    pub fn firmware_version(&mut self) -> Result<u16, E> {
+        let mut data = [0u8; 2];
+        self.i2c_read(Register::FirmwareVersion.addr(), &mut data)?;
+        Ok(u16::from_be_bytes(data))
+    }
+
+    /// Set the touch detection threshold, lower values are more sensitive
+    pub fn set_threshold(&mut self, threshold: u8) -> Result<(), E> {
+        self.write_config(Threshold(threshold))
+    }
+
+    /// Set how the controller drives the interrupt line
+    ///
+    /// The mode is kept in the configuration and applied again by
+    /// [`Self::init`], e.g. after [`Self::recover`].
+    pub fn set_interrupt_mode(&mut self, mode: InterruptMode) -> Result<(), E> {
+        self.write_config(mode)?;
+        self.config.interrupt_mode = mode;
+        Ok(())
+    }
+
+    /// Get the current power mode of the controller
+    pub fn power_mode(&self) -> PowerMode {
+        self.power_mode
     }
 
-    /// Set the interrupt flag (to be called from interrupt handler)
-    pub fn set_interrupt(&mut self) {
-        self.touch_int_flag = true;
+    /// Put the controller into deep sleep
+    ///
+    /// The controller stops scanning and no longer answers on I2C.
+    /// Use [`Self::wake`] to bring it back.
+    pub fn sleep(&mut self) -> Result<(), E> {
+        self.write_config(PowerMode::Sleep)?;
+        self.power_mode = PowerMode::Sleep;
+        self.touch_data.touch_num = 0;
+        self.motion.reset();
+        Ok(())
     }
 
-    /// Clear the interrupt flag
+    /// Enter the low-power gesture mode
+    ///
+    /// The controller scans at a reduced rate and only raises the interrupt
+    /// when one of the enabled `gestures` is detected. Read the gesture with
+    /// [`Self::read_wake_gesture`] and call [`Self::wake`] to resume normal
+    /// touch reporting.
+    pub fn enter_gesture_mode(&mut self, gestures: WakeGestures) -> Result<(), E> {
+        self.write_config(gestures)?;
+        self.write_config(GestureEnable(true))?;
+        self.write_config(PowerMode::Gesture)?;
+        self.power_mode = PowerMode::Gesture;
+        self.touch_data.touch_num = 0;
+        self.motion.reset();
+        Ok(())
+    }
+
+    /// Read the gesture that woke the controller
+    ///
+    /// This should be called after an interrupt occurs in gesture mode.
+    /// Returns None if the controller isn't in gesture mode or no known
+    /// gesture was reported.
+    pub fn read_wake_gesture(&mut self) -> Result<Option<WakeGesture>, E> {
+        if self.power_mode != PowerMode::Gesture {
+            return Ok(None);
+        }
+
+        self.interrupt.take();
+
+        self.read_config::<WakeGesture>()
+    }
+
+    /// Return the controller to normal touch reporting
+    ///
+    /// From deep sleep the controller has to be reset through its reset
+    /// line before it answers on I2C again, so the reset pin and a delay
+    /// are needed. From gesture mode only the mode registers are written.
+    pub fn wake<RST, D>(&mut self, reset: &mut RST, delay: &mut D) -> Result<(), E>
+    where
+        RST: OutputPin,
+        D: DelayNs,
+    {
+        if self.power_mode == PowerMode::Sleep {
+            self.hardware_reset(reset, delay, RESET_LOW_MS, RESET_BOOT_MS);
+            self.init()?;
+        } else {
+            self.write_config(GestureEnable(false))?;
+            self.write_config(PowerMode::Active)?;
+        }
+
+        self.power_mode = PowerMode::Active;
+        self.interrupt.take();
+        Ok(())
+    }
+
+    /// Pulse the controller's reset line
+    fn hardware_reset<RST, D>(&mut self, reset: &mut RST, delay: &mut D, low_ms: u32, boot_ms: u32)
+    where
+        RST: OutputPin,
+        D: DelayNs,
+    {
+        // The reset pin is only ever a plain GPIO, errors are not expected
+        let _ = reset.set_low();
+        delay.delay_ms(low_ms);
+        let _ = reset.set_high();
+        delay.delay_ms(boot_ms);
+    }
+
+    /// Discard a pending touch interrupt
     pub fn clear_interrupt(&mut self) {
-        self.touch_int_flag = false;
+        self.interrupt.take();
     }
 
     /// Check if there's a pending touch interrupt
     pub fn has_interrupt(&self) -> bool {
-        self.touch_int_flag
+        self.interrupt.is_pending()
+    }
+
+    /// Timestamp of the interrupt that triggered the last touch read (us)
+    pub fn last_interrupt_us(&self) -> u64 {
+        self.last_interrupt_us
     }
 
     /// Read touch data from the controller
     ///
-    /// This should be called after an interrupt occurs
+    /// This should be called after an interrupt occurs. The interrupt is
+    /// always taken, but nothing is read unless the controller is in
+    /// [`PowerMode::Active`]. Failed reads are counted
+    /// for the recovery policy, see [`Self::recover`].
     pub fn read_touch(&mut self) -> Result<(), E> {
-        if !self.touch_int_flag {
-            return Ok(());
+        // Take the interrupt in every mode, so it doesn't stay pending
+        match self.interrupt.take() {
+            Some(timestamp_us) => self.last_interrupt_us = timestamp_us,
+            None => return Ok(()),
         }
 
-        self.touch_int_flag = false;
+        self.read_frame()
+    }
 
-        let mut data = [0u8; 14];
-        println!("touch: i2c_read");
-        self.i2c_read(AXS5106L_TOUCH_DATA_REG, &mut data)?;
+    /// Read touch data without waiting for an interrupt
+    ///
+    /// Call this periodically while [`Self::has_touches`] is true and no
+    /// interrupt arrives: a latched controller stops pulsing its interrupt
+    /// line, and only a read notices the failure so [`Self::recover`] can
+    /// reset it. A pending interrupt is taken as well.
+    pub fn poll_touch(&mut self) -> Result<(), E> {
+        if let Some(timestamp_us) = self.interrupt.take() {
+            self.last_interrupt_us = timestamp_us;
+        }
+
+        self.read_frame()
+    }
+
+    /// Read and parse one touch frame if the controller is active
+    fn read_frame(&mut self) -> Result<(), E> {
+        if self.power_mode != PowerMode::Active {
+            return Ok(());
+        }
 
+        let mut data = [0u8; TOUCH_FRAME_LEN];
+        let result = self.i2c_read(Register::TouchData.addr(), &mut data);
+        self.track_read(result)?;
+
+        self.frame_count = self.frame_count.wrapping_add(1);
         self.touch_data.touch_num = data[1];
+        self.touch_data.timestamp_us = self.clock.now_us();
 
         if self.touch_data.touch_num == 0 {
+            self.rejection
+                .apply(self.config.rejection, &mut self.touch_data.coords, 0, 0);
+            self.filter.apply(self.config.filter, &mut []);
+            self.motion.update(&[], self.touch_data.timestamp_us);
             return Ok(());
         }
 
         // Parse touch coordinates
-        for i in 0..min(self.touch_data.touch_num, MAX_TOUCH_POINTS as u8) as usize {
+        for i in 0..self.point_count() {
             let base = 2 + i * 6;
 
+            // Finger ID in the upper nibble of the Y high byte
+            self.touch_data.coords[i].id = data[base + 2] >> 4;
+
             // Extract 12-bit X coordinate
             self.touch_data.coords[i].x = ((data[base] as u16 & 0x0F) << 8) | data[base + 1] as u16;
 
             // Extract 12-bit Y coordinate
             self.touch_data.coords[i].y =
                 ((data[base + 2] as u16 & 0x0F) << 8) | data[base + 3] as u16;
+
+            // Contact size
+            self.touch_data.coords[i].weight = data[base + 4];
+            self.touch_data.coords[i].area = data[base + 5];
         }
 
+        // Drop palms, crowds of points and unconfirmed fingers
+        let count = self.point_count();
+        let kept = self.rejection.apply(
+            self.config.rejection,
+            &mut self.touch_data.coords,
+            count,
+            data[1] as usize,
+        );
+        self.touch_data.touch_num = kept as u8;
+
+        // Filter in panel coordinates
+        self.filter
+            .apply(self.config.filter, &mut self.touch_data.coords[..kept]);
+
+        // Track motion in display coordinates
+        let (points, count) = self.display_points();
+        self.motion
+            .update(&points[..count], self.touch_data.timestamp_us);
+
         Ok(())
     }
 
+    /// Get the velocity and acceleration of a finger in display coordinates
+    ///
+    /// The estimate is kept after the finger is lifted, so it can be used
+    /// for fling scrolling when the `Up` event is handled. Returns None for
+    /// unknown finger IDs.
+    pub fn motion(&self, id: u8) -> Option<Motion> {
+        self.motion.motion(id)
+    }
+
     /// Get touch coordinates with rotation applied
     ///
     /// Returns None if there are no touches or if the internal touch data is invalid
@@ -178,50 +584,81 @@ where
         }
 
         let mut transformed = self.touch_data.clone();
+        let (points, count) = self.display_points();
+        transformed.coords[..count].copy_from_slice(&points[..count]);
 
-        // Apply rotation transformation to each touch point
-        for i in 0..min(self.touch_data.touch_num, MAX_TOUCH_POINTS as u8) as usize {
-            let (x, y) = match self.rotation {
-                Rotation::Rotate0 => {
-                    // Default orientation
-                    (
-                        self.width
-                            .saturating_sub(1)
-                            .saturating_sub(self.touch_data.coords[i].x),
-                        self.touch_data.coords[i].y,
-                    )
-                }
-                Rotation::Rotate90 => {
-                    // 90 degrees clockwise
-                    (self.touch_data.coords[i].y, self.touch_data.coords[i].x)
-                }
-                Rotation::Rotate180 => {
-                    // 180 degrees
-                    (
-                        self.touch_data.coords[i].x,
-                        self.height
-                            .saturating_sub(1)
-                            .saturating_sub(self.touch_data.coords[i].y),
-                    )
-                }
-                Rotation::Rotate270 => {
-                    // 270 degrees clockwise
-                    (
-                        self.height
-                            .saturating_sub(1)
-                            .saturating_sub(self.touch_data.coords[i].x),
-                        self.width
-                            .saturating_sub(1)
-                            .saturating_sub(self.touch_data.coords[i].y),
-                    )
-                }
-            };
-
-            transformed.coords[i].x = x;
-            transformed.coords[i].y = y;
+        Some(transformed)
+    }
+
+    /// Map a touch point from panel coordinates to display coordinates
+    ///
+    /// Scales from the native raw ranges to display pixels, applies the
+    /// calibration and then the display rotation.
+    fn transform(&self, point: Coordinates) -> Coordinates {
+        let config = &self.config;
+        let width = config.width;
+        let height = config.height;
+
+        // Scale to display pixels
+        let x = config.native_x.scale(point.x, width);
+        let y = config.native_y.scale(point.y, height);
+
+        // Calibrate
+        let cal = config.calibration;
+        let x = calibrate(x, cal.x_gain, cal.x_offset, width);
+        let y = calibrate(y, cal.y_gain, cal.y_offset, height);
+
+        let (x, y) = match config.rotation {
+            Rotation::Rotate0 => {
+                // Default orientation
+                (width.saturating_sub(1).saturating_sub(x), y)
+            }
+            Rotation::Rotate90 => {
+                // 90 degrees clockwise
+                (y, x)
+            }
+            Rotation::Rotate180 => {
+                // 180 degrees
+                (x, height.saturating_sub(1).saturating_sub(y))
+            }
+            Rotation::Rotate270 => {
+                // 270 degrees clockwise
+                (
+                    height.saturating_sub(1).saturating_sub(x),
+                    width.saturating_sub(1).saturating_sub(y),
+                )
+            }
+        };
+
+        Coordinates { x, y, ..point }
+    }
+
+    /// Current touch points in display coordinates, with their count
+    fn display_points(&self) -> ([Coordinates; MAX_TOUCH_POINTS], usize) {
+        let mut points = [Coordinates::default(); MAX_TOUCH_POINTS];
+        let count = self.point_count();
+        for (i, point) in points.iter_mut().enumerate().take(count) {
+            *point = self.transform(self.touch_data.coords[i]);
         }
+        (points, count)
+    }
 
-        Some(transformed)
+    /// Number of valid entries in `touch_data.coords`
+    fn point_count(&self) -> usize {
+        min(
+            self.touch_data.touch_num as usize,
+            min(MAX_FRAME_POINTS, MAX_TOUCH_POINTS),
+        )
+    }
+
+    /// Number of touch frames read since the driver was created
+    pub fn frame_count(&self) -> u32 {
+        self.frame_count
+    }
+
+    /// Number of touch frames dropped by the rejection policy
+    pub fn rejected_frames(&self) -> u32 {
+        self.rejection.rejected_frames
     }
 
     /// Get the number of current touches
@@ -234,3 +671,9 @@ where
         self.touch_data.touch_num > 0
     }
 }
+
+/// Apply gain (1/1000) and offset, clamped to `0..size`
+fn calibrate(value: u16, gain: u16, offset: i16, size: u16) -> u16 {
+    let value = value as i32 * gain as i32 / 1000 + offset as i32;
+    value.clamp(0, size.saturating_sub(1) as i32) as u16
+}
@@ -0,0 +1,303 @@
+//! Driver configuration
+//!
+//! [`Axs5106lConfig`] collects everything that differs between panel
+//! variants: I2C address and transaction style, the controller's native
+//! resolution, the display resolution and orientation, filtering,
+//! calibration, virtual keys, touch rejection and the interrupt mode.
+
+use super::{AXS5106L_ADDR, InterruptMode, RejectionPolicy, Rotation};
+
+/// How a register read is put on the bus
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Transaction {
+    /// Register address write and data read as two separate transactions,
+    /// with a STOP in between (what the vendor C++ driver does). On a shared
+    /// bus the bus is released between the two, so other devices can be
+    /// accessed while the controller prepares its data.
+    Separate,
+    /// A single write-read transaction with a repeated START
+    WriteRead,
+}
+
+/// Range of raw values the controller reports on one axis
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct AxisRange {
+    /// Raw value at the first display pixel
+    pub min: u16,
+    /// Raw value at the last display pixel
+    pub max: u16,
+}
+
+impl AxisRange {
+    /// Largest value of the 12-bit raw coordinates
+    pub const RAW_MAX: u16 = 0x0FFF;
+
+    /// Create a range of raw values
+    pub const fn new(min: u16, max: u16) -> Self {
+        Self { min, max }
+    }
+
+    /// Range of a controller reporting `0..size`
+    pub const fn from_size(size: u16) -> Self {
+        Self {
+            min: 0,
+            max: size.saturating_sub(1),
+        }
+    }
+
+    /// Map a raw value to `0..size` with rounding
+    ///
+    /// Values outside the range are clamped to its ends.
+    pub fn scale(&self, value: u16, size: u16) -> u16 {
+        let out_max = size.saturating_sub(1) as u32;
+        if self.max <= self.min {
+            return 0;
+        }
+
+        let span = (self.max - self.min) as u32;
+        let value = (value.clamp(self.min, self.max) - self.min) as u32;
+        ((value * out_max + span / 2) / span) as u16
+    }
+}
+
+/// Touch region in raw panel coordinates that acts as a key
+///
+/// Regions may lie outside the visible display area, e.g. capacitive keys
+/// printed on a bezel overlay. A finger that goes down inside a region
+/// produces `KeyPress` / `KeyRelease` events instead of pointer events.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct VirtualKey {
+    /// Name reported in key events
+    pub name: &'static str,
+    /// Raw X range of the region
+    pub x: AxisRange,
+    /// Raw Y range of the region
+    pub y: AxisRange,
+}
+
+impl VirtualKey {
+    /// Create a key covering the raw ranges `x` and `y` (inclusive)
+    pub const fn new(name: &'static str, x: AxisRange, y: AxisRange) -> Self {
+        Self { name, x, y }
+    }
+
+    /// Check if a raw point lies inside the region
+    pub fn contains(&self, x: u16, y: u16) -> bool {
+        (self.x.min..=self.x.max).contains(&x) && (self.y.min..=self.y.max).contains(&y)
+    }
+}
+
+/// Noise filter applied to every finger in panel coordinates
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct Filter {
+    /// Movements smaller than this are ignored (panel units), 0 disables
+    pub deadband: u16,
+    /// Weight of the previous position in percent (0..=99), 0 disables
+    pub smoothing: u8,
+}
+
+/// Linear correction applied after scaling to display pixels
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Calibration {
+    /// Horizontal gain in 1/1000
+    pub x_gain: u16,
+    /// Vertical gain in 1/1000
+    pub y_gain: u16,
+    /// Horizontal offset in display pixels
+    pub x_offset: i16,
+    /// Vertical offset in display pixels
+    pub y_offset: i16,
+}
+
+impl Default for Calibration {
+    fn default() -> Self {
+        Self {
+            x_gain: 1000,
+            y_gain: 1000,
+            x_offset: 0,
+            y_offset: 0,
+        }
+    }
+}
+
+/// Configuration for [`Axs5106l`](super::Axs5106l)
+///
+/// ```rust,ignore
+/// let config = Axs5106lConfig::new(172, 320)
+///     .with_address(0x63)
+///     .with_transaction(Transaction::WriteRead)
+///     .with_orientation(Rotation::Rotate0);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Axs5106lConfig {
+    pub(super) address: u8,
+    pub(super) transaction: Transaction,
+    pub(super) native_x: AxisRange,
+    pub(super) native_y: AxisRange,
+    pub(super) width: u16,
+    pub(super) height: u16,
+    pub(super) rotation: Rotation,
+    pub(super) filter: Filter,
+    pub(super) calibration: Calibration,
+    pub(super) virtual_keys: &'static [VirtualKey],
+    pub(super) rejection: RejectionPolicy,
+    pub(super) interrupt_mode: InterruptMode,
+}
+
+impl Axs5106lConfig {
+    /// Create a configuration for a display of `width` x `height` pixels
+    ///
+    /// The controller is assumed to report in display pixels, at address
+    /// 0x63, using separate write and read transactions, and to pulse its
+    /// interrupt line for every touch frame.
+    pub fn new(width: u16, height: u16) -> Self {
+        Self {
+            address: AXS5106L_ADDR,
+            transaction: Transaction::Separate,
+            native_x: AxisRange::from_size(width),
+            native_y: AxisRange::from_size(height),
+            width,
+            height,
+            rotation: Rotation::Rotate0,
+            filter: Filter::default(),
+            calibration: Calibration::default(),
+            virtual_keys: &[],
+            rejection: RejectionPolicy::default(),
+            interrupt_mode: InterruptMode::Pulse,
+        }
+    }
+
+    /// Set the 7-bit I2C address
+    pub fn with_address(mut self, address: u8) -> Self {
+        self.address = address;
+        self
+    }
+
+    /// Set how register reads are put on the bus
+    pub fn with_transaction(mut self, transaction: Transaction) -> Self {
+        self.transaction = transaction;
+        self
+    }
+
+    /// Set the resolution the controller reports in
+    ///
+    /// Shorthand for raw ranges of `0..width` and `0..height`.
+    pub fn with_native_resolution(mut self, width: u16, height: u16) -> Self {
+        self.native_x = AxisRange::from_size(width);
+        self.native_y = AxisRange::from_size(height);
+        self
+    }
+
+    /// Set the raw X and Y ranges the controller reports
+    ///
+    /// The raw values are scaled so `min` maps to the first and `max` to
+    /// the last display pixel, before the orientation is applied. Values
+    /// are limited to the controller's 12 bits.
+    pub fn with_native_range(mut self, x: AxisRange, y: AxisRange) -> Self {
+        self.native_x =
+            AxisRange::new(x.min.min(AxisRange::RAW_MAX), x.max.min(AxisRange::RAW_MAX));
+        self.native_y =
+            AxisRange::new(y.min.min(AxisRange::RAW_MAX), y.max.min(AxisRange::RAW_MAX));
+        self
+    }
+
+    /// Set the display resolution in pixels
+    ///
+    /// The native ranges are left as they are, so touches are scaled from
+    /// the controller's resolution to the new one. If the controller also
+    /// reports in the new resolution, set it with
+    /// [`Self::with_native_resolution`] as well.
+    pub fn with_display_resolution(mut self, width: u16, height: u16) -> Self {
+        self.width = width;
+        self.height = height;
+        self
+    }
+
+    /// Set the display orientation
+    pub fn with_orientation(mut self, rotation: Rotation) -> Self {
+        self.rotation = rotation;
+        self
+    }
+
+    /// Set the noise filter
+    pub fn with_filter(mut self, filter: Filter) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    /// Set the linear calibration
+    pub fn with_calibration(mut self, calibration: Calibration) -> Self {
+        self.calibration = calibration;
+        self
+    }
+
+    /// Set touch regions that act as keys instead of pointer input
+    pub fn with_virtual_keys(mut self, keys: &'static [VirtualKey]) -> Self {
+        self.virtual_keys = keys;
+        self
+    }
+
+    /// Set the palm and large-contact rejection limits
+    pub fn with_rejection(mut self, rejection: RejectionPolicy) -> Self {
+        self.rejection = rejection;
+        self
+    }
+
+    /// Set how the controller drives the interrupt line
+    ///
+    /// Written by `init()`, so it is applied again after every reset.
+    pub fn with_interrupt_mode(mut self, mode: InterruptMode) -> Self {
+        self.interrupt_mode = mode;
+        self
+    }
+
+    /// Get the I2C address
+    pub fn address(&self) -> u8 {
+        self.address
+    }
+
+    /// Get the transaction style
+    pub fn transaction(&self) -> Transaction {
+        self.transaction
+    }
+
+    /// Get the raw ranges as (x, y)
+    pub fn native_range(&self) -> (AxisRange, AxisRange) {
+        (self.native_x, self.native_y)
+    }
+
+    /// Get the display resolution as (width, height)
+    pub fn display_resolution(&self) -> (u16, u16) {
+        (self.width, self.height)
+    }
+
+    /// Get the display orientation
+    pub fn orientation(&self) -> Rotation {
+        self.rotation
+    }
+
+    /// Get the noise filter
+    pub fn filter(&self) -> Filter {
+        self.filter
+    }
+
+    /// Get the linear calibration
+    pub fn calibration(&self) -> Calibration {
+        self.calibration
+    }
+
+    /// Get the rejection limits
+    pub fn rejection(&self) -> RejectionPolicy {
+        self.rejection
+    }
+
+    /// Get the interrupt mode
+    pub fn interrupt_mode(&self) -> InterruptMode {
+        self.interrupt_mode
+    }
+
+    /// Get the virtual key regions
+    pub fn virtual_keys(&self) -> &'static [VirtualKey] {
+        self.virtual_keys
+    }
+}
@@ -0,0 +1,337 @@
+//! Touch events and an ISR-safe event queue
+//!
+//! [`Axs5106l::read_touch_events`] compares each new touch frame with the
+//! previous one and turns the difference into [`TouchEvent`]s, with key
+//! events for fingers on virtual key regions. The events
+//! go into a fixed-capacity [`TouchEventQueue`] that the UI loop drains at
+//! its own pace, so slow redraws don't lose touches.
+
+use core::cell::RefCell;
+
+use critical_section::Mutex;
+use embedded_hal::i2c::I2c;
+
+use crate::clock::Clock;
+
+use super::{Axs5106l, TouchData};
+
+/// Pointer event in display coordinates
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum TouchEvent {
+    /// A finger touched the panel
+    Down {
+        id: u8,
+        x: u16,
+        y: u16,
+        timestamp_us: u64,
+    },
+    /// A finger moved while touching the panel
+    Move {
+        id: u8,
+        x: u16,
+        y: u16,
+        timestamp_us: u64,
+    },
+    /// A finger was lifted, at its last known position
+    Up {
+        id: u8,
+        x: u16,
+        y: u16,
+        timestamp_us: u64,
+    },
+    /// A finger went down inside a virtual key region
+    KeyPress {
+        id: u8,
+        key: &'static str,
+        timestamp_us: u64,
+    },
+    /// The finger pressing a virtual key was lifted
+    KeyRelease {
+        id: u8,
+        key: &'static str,
+        timestamp_us: u64,
+    },
+}
+
+impl TouchEvent {
+    /// Finger ID of the event
+    pub fn id(&self) -> u8 {
+        match *self {
+            TouchEvent::Down { id, .. }
+            | TouchEvent::Move { id, .. }
+            | TouchEvent::Up { id, .. }
+            | TouchEvent::KeyPress { id, .. }
+            | TouchEvent::KeyRelease { id, .. } => id,
+        }
+    }
+
+    /// Time of the touch frame that produced the event (us)
+    pub fn timestamp_us(&self) -> u64 {
+        match *self {
+            TouchEvent::Down { timestamp_us, .. }
+            | TouchEvent::Move { timestamp_us, .. }
+            | TouchEvent::Up { timestamp_us, .. }
+            | TouchEvent::KeyPress { timestamp_us, .. }
+            | TouchEvent::KeyRelease { timestamp_us, .. } => timestamp_us,
+        }
+    }
+
+    /// Position of a pointer event, None for key events
+    pub fn position(&self) -> Option<(u16, u16)> {
+        match *self {
+            TouchEvent::Down { x, y, .. }
+            | TouchEvent::Move { x, y, .. }
+            | TouchEvent::Up { x, y, .. } => Some((x, y)),
+            TouchEvent::KeyPress { .. } | TouchEvent::KeyRelease { .. } => None,
+        }
+    }
+}
+
+struct Ring<const N: usize> {
+    events: [TouchEvent; N],
+    head: usize,
+    len: usize,
+    overflows: u32,
+}
+
+/// Fixed-capacity queue of touch events
+///
+/// All accesses go through a critical section, so the queue can be filled
+/// from an interrupt handler or another task while the UI loop drains it.
+/// A `Move` is merged into a `Move` of the same finger at the tail of the
+/// queue. When the queue is full new events are dropped and counted.
+pub struct TouchEventQueue<const N: usize> {
+    ring: Mutex<RefCell<Ring<N>>>,
+}
+
+impl<const N: usize> TouchEventQueue<N> {
+    /// Create an empty queue, usable in a `static`
+    ///
+    /// `N` must be at least 1, checked at compile time.
+    pub const fn new() -> Self {
+        const { assert!(N > 0, "TouchEventQueue needs a capacity of at least 1") };
+        Self {
+            ring: Mutex::new(RefCell::new(Ring {
+                events: [TouchEvent::Up {
+                    id: 0,
+                    x: 0,
+                    y: 0,
+                    timestamp_us: 0,
+                }; N],
+                head: 0,
+                len: 0,
+                overflows: 0,
+            })),
+        }
+    }
+
+    /// Add an event to the queue
+    ///
+    /// Returns false if the queue was full and the event was dropped.
+    pub fn push(&self, event: TouchEvent) -> bool {
+        critical_section::with(|cs| {
+            let mut ring = self.ring.borrow_ref_mut(cs);
+
+            // Coalesce consecutive moves of the same finger
+            if let TouchEvent::Move { id, .. } = event
+                && ring.len > 0
+            {
+                let tail = (ring.head + ring.len - 1) % N;
+                if matches!(ring.events[tail], TouchEvent::Move { id: tail_id, .. } if tail_id == id)
+                {
+                    ring.events[tail] = event;
+                    return true;
+                }
+            }
+
+            if ring.len == N {
+                ring.overflows = ring.overflows.wrapping_add(1);
+                return false;
+            }
+
+            let index = (ring.head + ring.len) % N;
+            ring.events[index] = event;
+            ring.len += 1;
+            true
+        })
+    }
+
+    /// Remove the oldest event from the queue
+    pub fn pop(&self) -> Option<TouchEvent> {
+        critical_section::with(|cs| {
+            let mut ring = self.ring.borrow_ref_mut(cs);
+            if ring.len == 0 {
+                return None;
+            }
+
+            let event = ring.events[ring.head];
+            ring.head = (ring.head + 1) % N;
+            ring.len -= 1;
+            Some(event)
+        })
+    }
+
+    /// Discard all queued events
+    pub fn clear(&self) {
+        critical_section::with(|cs| {
+            let mut ring = self.ring.borrow_ref_mut(cs);
+            ring.head = 0;
+            ring.len = 0;
+        });
+    }
+
+    /// Discard the newest events until at most `len` are left
+    ///
+    /// Drops the events of a read without touching older ones: note
+    /// [`Self::len`] before the read and truncate to it afterwards. A
+    /// `Move` merged into an older queued `Move` is kept.
+    pub fn truncate(&self, len: usize) {
+        critical_section::with(|cs| {
+            let mut ring = self.ring.borrow_ref_mut(cs);
+            ring.len = ring.len.min(len);
+        });
+    }
+
+    /// Number of queued events
+    pub fn len(&self) -> usize {
+        critical_section::with(|cs| self.ring.borrow_ref(cs).len)
+    }
+
+    /// Check if the queue is empty
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Number of events dropped because the queue was full
+    pub fn overflow_count(&self) -> u32 {
+        critical_section::with(|cs| self.ring.borrow_ref(cs).overflows)
+    }
+}
+
+impl<const N: usize> Default for TouchEventQueue<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<I2C, E, C> Axs5106l<I2C, C>
+where
+    I2C: I2c<Error = E>,
+    C: Clock,
+{
+    /// Read touch data and push the resulting events into `queue`
+    ///
+    /// This should be called after an interrupt occurs, instead of
+    /// [`Self::read_touch`]. Events use display coordinates.
+    pub fn read_touch_events<const N: usize>(
+        &mut self,
+        queue: &TouchEventQueue<N>,
+    ) -> Result<(), E> {
+        let frame = self.frame_count;
+        self.read_touch()?;
+        if self.frame_count != frame {
+            self.emit_events(queue);
+        }
+        Ok(())
+    }
+
+    /// Poll touch data and push the resulting events into `queue`
+    ///
+    /// The event version of [`Self::poll_touch`].
+    pub fn poll_touch_events<const N: usize>(
+        &mut self,
+        queue: &TouchEventQueue<N>,
+    ) -> Result<(), E> {
+        let frame = self.frame_count;
+        self.poll_touch()?;
+        if self.frame_count != frame {
+            self.emit_events(queue);
+        }
+        Ok(())
+    }
+
+    /// Compare the current frame with the last reported one
+    fn emit_events<const N: usize>(&mut self, queue: &TouchEventQueue<N>) {
+        let (current, count) = self.display_points();
+        let raw = &self.touch_data.coords[..count];
+        let timestamp_us = self.touch_data.timestamp_us;
+        let keys = self.config.virtual_keys;
+
+        // Fingers on virtual keys that are no longer reported were lifted
+        for slot in self.pressed_keys.iter_mut() {
+            if let Some((id, key)) = *slot
+                && !raw.iter().any(|p| p.id == id)
+            {
+                queue.push(TouchEvent::KeyRelease {
+                    id,
+                    key: keys[key].name,
+                    timestamp_us,
+                });
+                *slot = None;
+            }
+        }
+
+        // Pointer fingers that are no longer reported were lifted
+        let previous = &self.reported.coords[..self.reported.touch_num as usize];
+        for last in previous {
+            if !current[..count].iter().any(|p| p.id == last.id) {
+                queue.push(TouchEvent::Up {
+                    id: last.id,
+                    x: last.x,
+                    y: last.y,
+                    timestamp_us,
+                });
+            }
+        }
+
+        let mut pointers = TouchData::default();
+        for (point, raw) in current[..count].iter().zip(raw) {
+            // A finger stays on its key until it is lifted
+            if self
+                .pressed_keys
+                .iter()
+                .flatten()
+                .any(|&(id, _)| id == point.id)
+            {
+                continue;
+            }
+
+            match previous.iter().find(|p| p.id == point.id) {
+                Some(last) if last.x == point.x && last.y == point.y => {}
+                Some(_) => {
+                    queue.push(TouchEvent::Move {
+                        id: point.id,
+                        x: point.x,
+                        y: point.y,
+                        timestamp_us,
+                    });
+                }
+                None => {
+                    if let Some(key) = keys.iter().position(|k| k.contains(raw.x, raw.y))
+                        && let Some(slot) = self.pressed_keys.iter_mut().find(|s| s.is_none())
+                    {
+                        *slot = Some((point.id, key));
+                        queue.push(TouchEvent::KeyPress {
+                            id: point.id,
+                            key: keys[key].name,
+                            timestamp_us,
+                        });
+                        continue;
+                    }
+
+                    queue.push(TouchEvent::Down {
+                        id: point.id,
+                        x: point.x,
+                        y: point.y,
+                        timestamp_us,
+                    });
+                }
+            }
+
+            pointers.coords[pointers.touch_num as usize] = *point;
+            pointers.touch_num += 1;
+        }
+
+        self.reported = pointers;
+    }
+}
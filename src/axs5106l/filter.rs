@@ -0,0 +1,43 @@
+//! Per-finger noise filter
+//!
+//! Applies the deadband and exponential smoothing from [`Filter`] to raw
+//! panel coordinates, keyed by the controller's finger ID.
+
+use super::config::Filter;
+use super::{Coordinates, MAX_TOUCH_POINTS};
+
+/// Last filtered position of every finger
+#[derive(Default)]
+pub(super) struct FilterState {
+    last: [Option<Coordinates>; MAX_TOUCH_POINTS],
+}
+
+impl FilterState {
+    /// Filter a frame of points in place
+    pub(super) fn apply(&mut self, filter: Filter, points: &mut [Coordinates]) {
+        let mut next = [None; MAX_TOUCH_POINTS];
+
+        for (slot, point) in points.iter_mut().enumerate().take(MAX_TOUCH_POINTS) {
+            if let Some(last) = self.last.iter().flatten().find(|p| p.id == point.id) {
+                *point = Coordinates {
+                    x: Self::filter_axis(filter, last.x, point.x),
+                    y: Self::filter_axis(filter, last.y, point.y),
+                    ..*point
+                };
+            }
+            next[slot] = Some(*point);
+        }
+
+        // Fingers missing from the frame are forgotten
+        self.last = next;
+    }
+
+    fn filter_axis(filter: Filter, last: u16, new: u16) -> u16 {
+        if last.abs_diff(new) < filter.deadband {
+            return last;
+        }
+
+        let weight = filter.smoothing.min(99) as u32;
+        ((last as u32 * weight + new as u32 * (100 - weight) + 50) / 100) as u16
+    }
+}
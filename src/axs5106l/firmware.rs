@@ -0,0 +1,271 @@
+//! Firmware download for the AXS5106L
+//!
+//! Compares the on-chip firmware version with the bundled image, and if
+//! the chip is older puts it into download mode, erases the flash, writes
+//! the image in 32-byte chunks, reads every chunk back to verify it and
+//! finally pulses the reset line so the controller boots the new image.
+//!
+//! **Unverified, only built with the `unverified-fw-download` feature.**
+//! Neither the AXS5106L datasheet nor the vendor driver this crate was
+//! ported from documents the download registers 0xF0-0xF3
+//! ([`Register::DownloadControl`] to [`Register::DownloadData`]), the
+//! command values written to them, the busy bit, the image layout
+//! (version at 0x0C) or the flash size. They are placeholders for the
+//! vendor's download protocol, and a wrong guess can leave a panel without
+//! working firmware. Until they are ported from the vendor's flashing code
+//! and cited here, only enable the feature to test on a spare panel. The
+//! controller is always reset through its reset line at the end, whether
+//! the download succeeded or not, so a failed download doesn't leave it in
+//! download mode.
+
+use embedded_hal::delay::DelayNs;
+use embedded_hal::digital::OutputPin;
+use embedded_hal::i2c::I2c;
+
+use crate::clock::Clock;
+
+use super::{
+    Axs5106l, I2C_WRITE_MAX, PowerMode, RESET_BOOT_MS, RESET_LOW_MS, Register, WritableRegister,
+};
+
+/// Offset of the big-endian firmware version inside an image
+const IMAGE_VERSION_OFFSET: usize = 0x0C;
+
+/// Smallest image that can hold a version
+const IMAGE_MIN_LEN: usize = IMAGE_VERSION_OFFSET + 2;
+
+/// Size of the controller's program flash
+const FLASH_SIZE: usize = 64 * 1024;
+
+/// Poll interval while waiting for the flash (ms)
+const BUSY_POLL_MS: u32 = 5;
+
+/// Longest time to wait for a flash erase (ms)
+const ERASE_TIMEOUT_MS: u32 = 2_000;
+
+/// Longest time to wait for a chunk to be programmed (ms)
+const WRITE_TIMEOUT_MS: u32 = 50;
+
+/// Commands written to [`Register::DownloadControl`], unverified
+#[derive(Clone, Copy)]
+enum DownloadCommand {
+    Enter = 0xA5,
+    Erase = 0x5A,
+}
+
+/// Firmware update step reported to the progress callback
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum FirmwarePhase {
+    Erase,
+    Write,
+    Verify,
+}
+
+/// Progress of a firmware update
+#[derive(Clone, Copy, Debug)]
+pub struct FirmwareProgress {
+    pub phase: FirmwarePhase,
+    /// Bytes done in this phase
+    pub done: usize,
+    /// Total bytes in this phase
+    pub total: usize,
+}
+
+/// Result of a successful firmware update call
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum FirmwareUpdate {
+    /// The chip already runs this or a newer version, nothing was written
+    UpToDate { version: u16 },
+    /// The image was written and the chip rebooted into it
+    Updated { from: u16, to: u16 },
+}
+
+/// Firmware update errors
+#[derive(Debug)]
+pub enum FirmwareError<E> {
+    /// I2C communication failed
+    I2c(E),
+    /// The image is too short to contain a version
+    ImageTooSmall,
+    /// The image doesn't fit into the controller's flash
+    ImageTooLarge,
+    /// The flash stayed busy for too long
+    Timeout,
+    /// Read-back differs from the image at the given offset
+    VerifyFailed { offset: usize },
+    /// The chip doesn't report the image version after rebooting
+    VersionMismatch { expected: u16, found: u16 },
+}
+
+/// Get the firmware version stored in an image
+///
+/// Returns None if the image is too short.
+pub fn image_version(image: &[u8]) -> Option<u16> {
+    if image.len() < IMAGE_MIN_LEN {
+        return None;
+    }
+    Some(u16::from_be_bytes([
+        image[IMAGE_VERSION_OFFSET],
+        image[IMAGE_VERSION_OFFSET + 1],
+    ]))
+}
+
+impl<I2C, E, C> Axs5106l<I2C, C>
+where
+    I2C: I2c<Error = E>,
+    C: Clock,
+{
+    /// Update the controller firmware if the chip runs an older version
+    ///
+    /// # Arguments
+    /// * `image` - Firmware image, the version is read from the image header
+    /// * `reset` - Reset line of the controller
+    /// * `delay` - Delay used while waiting for the flash and the reset
+    /// * `progress` - Called after every erase, write and verify step
+    ///
+    /// Once the download has started the controller is reset through
+    /// `reset` and `init()` is run again, also when the download fails, so
+    /// the chip is never left in download mode. After a failed download it
+    /// runs whatever is left in its flash; retry the update.
+    pub fn update_firmware<RST, D, F>(
+        &mut self,
+        image: &[u8],
+        reset: &mut RST,
+        delay: &mut D,
+        progress: F,
+    ) -> Result<FirmwareUpdate, FirmwareError<E>>
+    where
+        RST: OutputPin,
+        D: DelayNs,
+        F: FnMut(FirmwareProgress),
+    {
+        let new_version = image_version(image).ok_or(FirmwareError::ImageTooSmall)?;
+        if image.len() > FLASH_SIZE {
+            return Err(FirmwareError::ImageTooLarge);
+        }
+
+        let current = self.firmware_version().map_err(FirmwareError::I2c)?;
+        if current >= new_version {
+            return Ok(FirmwareUpdate::UpToDate { version: current });
+        }
+
+        let result = self.download(image, delay, progress);
+
+        // Boot whatever is in flash now, like recover() does
+        self.hardware_reset(reset, delay, RESET_LOW_MS, RESET_BOOT_MS);
+        self.power_mode = PowerMode::Active;
+        self.touch_data.touch_num = 0;
+        self.motion.reset();
+        self.interrupt.take();
+        result?;
+        self.init().map_err(FirmwareError::I2c)?;
+
+        let found = self.firmware_version().map_err(FirmwareError::I2c)?;
+        if found != new_version {
+            return Err(FirmwareError::VersionMismatch {
+                expected: new_version,
+                found,
+            });
+        }
+
+        Ok(FirmwareUpdate::Updated {
+            from: current,
+            to: new_version,
+        })
+    }
+
+    /// Erase the flash, write `image` and read it back
+    fn download<D, F>(
+        &mut self,
+        image: &[u8],
+        delay: &mut D,
+        mut progress: F,
+    ) -> Result<(), FirmwareError<E>>
+    where
+        D: DelayNs,
+        F: FnMut(FirmwareProgress),
+    {
+        // Erase
+        self.download_command(DownloadCommand::Enter)?;
+        progress(FirmwareProgress {
+            phase: FirmwarePhase::Erase,
+            done: 0,
+            total: image.len(),
+        });
+        self.download_command(DownloadCommand::Erase)?;
+        self.wait_flash_ready(delay, ERASE_TIMEOUT_MS)?;
+        progress(FirmwareProgress {
+            phase: FirmwarePhase::Erase,
+            done: image.len(),
+            total: image.len(),
+        });
+
+        // Write
+        for (index, chunk) in image.chunks(I2C_WRITE_MAX).enumerate() {
+            let offset = index * I2C_WRITE_MAX;
+            self.set_download_address(offset)?;
+            self.i2c_write(WritableRegister::DownloadData.addr(), chunk)
+                .map_err(FirmwareError::I2c)?;
+            self.wait_flash_ready(delay, WRITE_TIMEOUT_MS)?;
+            progress(FirmwareProgress {
+                phase: FirmwarePhase::Write,
+                done: offset + chunk.len(),
+                total: image.len(),
+            });
+        }
+
+        // Verify
+        let mut readback = [0u8; I2C_WRITE_MAX];
+        for (index, chunk) in image.chunks(I2C_WRITE_MAX).enumerate() {
+            let offset = index * I2C_WRITE_MAX;
+            let readback = &mut readback[..chunk.len()];
+            self.set_download_address(offset)?;
+            self.i2c_read(Register::DownloadData.addr(), readback)
+                .map_err(FirmwareError::I2c)?;
+            if let Some(pos) = chunk.iter().zip(readback.iter()).position(|(a, b)| a != b) {
+                return Err(FirmwareError::VerifyFailed {
+                    offset: offset + pos,
+                });
+            }
+            progress(FirmwareProgress {
+                phase: FirmwarePhase::Verify,
+                done: offset + chunk.len(),
+                total: image.len(),
+            });
+        }
+        Ok(())
+    }
+
+    fn download_command(&mut self, command: DownloadCommand) -> Result<(), FirmwareError<E>> {
+        self.write_register(WritableRegister::DownloadControl, command as u8)
+            .map_err(FirmwareError::I2c)
+    }
+
+    fn set_download_address(&mut self, offset: usize) -> Result<(), FirmwareError<E>> {
+        let addr = (offset as u32).to_be_bytes();
+        self.i2c_write(WritableRegister::DownloadAddress.addr(), &addr[1..])
+            .map_err(FirmwareError::I2c)
+    }
+
+    /// Poll the download status until the flash is idle
+    fn wait_flash_ready<D: DelayNs>(
+        &mut self,
+        delay: &mut D,
+        timeout_ms: u32,
+    ) -> Result<(), FirmwareError<E>> {
+        let mut waited_ms = 0;
+        loop {
+            let status = self
+                .read_register(Register::DownloadStatus)
+                .map_err(FirmwareError::I2c)?;
+            if status & 0x01 == 0 {
+                return Ok(());
+            }
+            if waited_ms >= timeout_ms {
+                return Err(FirmwareError::Timeout);
+            }
+            delay.delay_ms(BUSY_POLL_MS);
+            waited_ms += BUSY_POLL_MS;
+        }
+    }
+}
@@ -0,0 +1,85 @@
+//! ISR-safe hand-over of touch interrupts
+//!
+//! The GPIO interrupt handler can't reach the driver, which is owned by the
+//! main loop. Instead the handler signals a `static` [`TouchInterrupt`] and
+//! the driver takes the pending edge from it in
+//! [`Axs5106l::read_touch`](super::Axs5106l::read_touch).
+
+use core::cell::Cell;
+
+use critical_section::Mutex;
+
+#[derive(Clone, Copy)]
+struct State {
+    pending: bool,
+    timestamp_us: u64,
+    edges: u32,
+}
+
+/// Pending touch interrupt, shared between the ISR and the driver
+///
+/// All accesses go through a critical section, so [`Self::signal`] can be
+/// called from an interrupt handler while the main loop is using the driver.
+pub struct TouchInterrupt {
+    state: Mutex<Cell<State>>,
+}
+
+impl TouchInterrupt {
+    /// Create a new interrupt cell, usable in a `static`
+    pub const fn new() -> Self {
+        Self {
+            state: Mutex::new(Cell::new(State {
+                pending: false,
+                timestamp_us: 0,
+                edges: 0,
+            })),
+        }
+    }
+
+    /// Record a falling edge on the interrupt line (to be called from the ISR)
+    ///
+    /// If an edge is already pending its timestamp is kept, so the driver
+    /// sees when the oldest unhandled edge happened.
+    pub fn signal(&self, timestamp_us: u64) {
+        critical_section::with(|cs| {
+            let cell = self.state.borrow(cs);
+            let mut state = cell.get();
+            if !state.pending {
+                state.pending = true;
+                state.timestamp_us = timestamp_us;
+            }
+            state.edges = state.edges.wrapping_add(1);
+            cell.set(state);
+        });
+    }
+
+    /// Take the pending edge, returning its timestamp in microseconds
+    pub fn take(&self) -> Option<u64> {
+        critical_section::with(|cs| {
+            let cell = self.state.borrow(cs);
+            let mut state = cell.get();
+            if !state.pending {
+                return None;
+            }
+            state.pending = false;
+            cell.set(state);
+            Some(state.timestamp_us)
+        })
+    }
+
+    /// Check if an edge is pending without taking it
+    pub fn is_pending(&self) -> bool {
+        critical_section::with(|cs| self.state.borrow(cs).get().pending)
+    }
+
+    /// Total number of edges signalled, including coalesced ones
+    pub fn edge_count(&self) -> u32 {
+        critical_section::with(|cs| self.state.borrow(cs).get().edges)
+    }
+}
+
+impl Default for TouchInterrupt {
+    fn default() -> Self {
+        Self::new()
+    }
+}
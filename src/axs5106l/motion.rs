@@ -0,0 +1,149 @@
+//! Per-finger velocity and acceleration estimates
+//!
+//! Updated from every touch frame in display coordinates. Velocities are
+//! smoothed with an exponential filter so single noisy samples don't cause
+//! spikes in fling or gesture detection.
+
+use super::{Coordinates, MAX_TOUCH_POINTS};
+
+/// Weight of the newest sample in the smoothed velocity (0..1)
+const SMOOTHING: f32 = 0.5;
+
+/// Samples further apart than this restart the estimate (us)
+const MAX_SAMPLE_GAP_US: u64 = 100_000;
+
+/// Velocity and acceleration of a finger in display coordinates
+#[derive(Clone, Copy, Default, Debug)]
+pub struct Motion {
+    /// Horizontal velocity in px/s
+    pub vx: f32,
+    /// Vertical velocity in px/s
+    pub vy: f32,
+    /// Horizontal acceleration in px/s²
+    pub ax: f32,
+    /// Vertical acceleration in px/s²
+    pub ay: f32,
+}
+
+impl Motion {
+    /// Speed in px/s
+    pub fn speed(&self) -> f32 {
+        sqrt(self.vx * self.vx + self.vy * self.vy)
+    }
+}
+
+/// Square root without the standard library
+fn sqrt(value: f32) -> f32 {
+    if value <= 0.0 {
+        return 0.0;
+    }
+    // Newton iterations starting from a bit-level estimate
+    let mut x = f32::from_bits((value.to_bits() >> 1) + 0x1FBD_1DF5);
+    for _ in 0..3 {
+        x = 0.5 * (x + value / x);
+    }
+    x
+}
+
+#[derive(Clone, Copy, Default)]
+struct Track {
+    id: u8,
+    active: bool,
+    x: u16,
+    y: u16,
+    timestamp_us: u64,
+    motion: Motion,
+}
+
+/// Velocity trackers for all fingers
+#[derive(Default)]
+pub(super) struct MotionTracker {
+    tracks: [Option<Track>; MAX_TOUCH_POINTS],
+}
+
+impl MotionTracker {
+    /// Update the trackers with a frame of points taken at `timestamp_us`
+    pub(super) fn update(&mut self, points: &[Coordinates], timestamp_us: u64) {
+        // Fingers missing from the frame were lifted, keep their last estimate
+        for track in self.tracks.iter_mut().flatten() {
+            if !points.iter().any(|p| p.id == track.id) {
+                track.active = false;
+            }
+        }
+
+        for point in points {
+            let slot = self
+                .tracks
+                .iter()
+                .position(|t| t.is_some_and(|t| t.id == point.id))
+                .or_else(|| self.tracks.iter().position(|t| t.is_none_or(|t| !t.active)));
+            let Some(slot) = slot else {
+                continue;
+            };
+
+            let track = match self.tracks[slot] {
+                Some(track) if track.id == point.id && track.active => {
+                    Self::advance(track, point, timestamp_us)
+                }
+                // New finger, or an ID reused after a release
+                _ => Track {
+                    id: point.id,
+                    active: true,
+                    x: point.x,
+                    y: point.y,
+                    timestamp_us,
+                    motion: Motion::default(),
+                },
+            };
+            self.tracks[slot] = Some(track);
+        }
+    }
+
+    fn advance(track: Track, point: &Coordinates, timestamp_us: u64) -> Track {
+        let dt_us = timestamp_us.saturating_sub(track.timestamp_us);
+        if dt_us == 0 {
+            return track;
+        }
+
+        let mut next = Track {
+            x: point.x,
+            y: point.y,
+            timestamp_us,
+            ..track
+        };
+
+        if dt_us > MAX_SAMPLE_GAP_US {
+            next.motion = Motion::default();
+            return next;
+        }
+
+        let dt = dt_us as f32 / 1_000_000.0;
+        let vx = (point.x as f32 - track.x as f32) / dt;
+        let vy = (point.y as f32 - track.y as f32) / dt;
+        let last = track.motion;
+        let vx = last.vx + SMOOTHING * (vx - last.vx);
+        let vy = last.vy + SMOOTHING * (vy - last.vy);
+
+        next.motion = Motion {
+            vx,
+            vy,
+            ax: (vx - last.vx) / dt,
+            ay: (vy - last.vy) / dt,
+        };
+        next
+    }
+
+    /// Latest estimate for a finger, also after it was lifted
+    pub(super) fn motion(&self, id: u8) -> Option<Motion> {
+        self.tracks
+            .iter()
+            .flatten()
+            .find(|t| t.id == id)
+            .map(|t| t.motion)
+    }
+
+    /// Forget all fingers
+    pub(super) fn reset(&mut self) {
+        self.tracks = Default::default();
+    }
+}
@@ -0,0 +1,145 @@
+//! Recovery from a latched-up touch controller
+//!
+//! After ESD events the AXS5106L can stop answering on I2C until it is
+//! reset. The driver counts consecutive failed reads; once the configured
+//! threshold is reached [`Axs5106l::recover`] pulses the reset line and
+//! re-runs [`Axs5106l::init`].
+
+use embedded_hal::delay::DelayNs;
+use embedded_hal::digital::OutputPin;
+use embedded_hal::i2c::I2c;
+
+use crate::clock::Clock;
+
+use super::{Axs5106l, PowerMode};
+
+/// Limits for automatic recovery
+#[derive(Clone, Copy, Debug)]
+pub struct RecoveryPolicy {
+    /// Consecutive failed reads before a recovery is attempted
+    pub failure_threshold: u8,
+    /// Recovery attempts before giving up, 0 retries forever
+    pub max_attempts: u8,
+    /// Time the reset line is held low (ms)
+    pub reset_low_ms: u32,
+    /// Time to wait after releasing reset before re-initialising (ms)
+    pub boot_delay_ms: u32,
+}
+
+impl Default for RecoveryPolicy {
+    fn default() -> Self {
+        Self {
+            failure_threshold: 5,
+            max_attempts: 3,
+            reset_low_ms: 20,
+            boot_delay_ms: 200,
+        }
+    }
+}
+
+/// Outcome of a recovery attempt
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum RecoveryEvent {
+    /// The controller answered again after the given attempt
+    Recovered { attempt: u8 },
+    /// The given attempt failed, another one will follow
+    Failed { attempt: u8 },
+    /// All attempts failed, no further recovery until a read succeeds
+    GaveUp,
+}
+
+impl<I2C, E, C> Axs5106l<I2C, C>
+where
+    I2C: I2c<Error = E>,
+    C: Clock,
+{
+    /// Set the recovery limits
+    pub fn set_recovery_policy(&mut self, policy: RecoveryPolicy) {
+        self.recovery = policy;
+    }
+
+    /// Get the recovery limits
+    pub fn recovery_policy(&self) -> &RecoveryPolicy {
+        &self.recovery
+    }
+
+    /// Number of consecutive failed touch reads
+    pub fn consecutive_failures(&self) -> u8 {
+        self.consecutive_failures
+    }
+
+    /// Total number of failed touch reads since the driver was created
+    pub fn error_count(&self) -> u32 {
+        self.error_count
+    }
+
+    /// Total number of successful recoveries since the driver was created
+    pub fn recovery_count(&self) -> u32 {
+        self.recovery_count
+    }
+
+    /// Check if the failure threshold is reached and attempts are left
+    pub fn needs_recovery(&self) -> bool {
+        self.consecutive_failures >= self.recovery.failure_threshold && !self.recovery_exhausted()
+    }
+
+    fn recovery_exhausted(&self) -> bool {
+        self.recovery.max_attempts != 0 && self.recovery_attempts >= self.recovery.max_attempts
+    }
+
+    /// Record the result of an I2C read for the recovery policy
+    pub(super) fn track_read<T>(&mut self, result: Result<T, E>) -> Result<T, E> {
+        match result {
+            Ok(value) => {
+                self.consecutive_failures = 0;
+                self.recovery_attempts = 0;
+                Ok(value)
+            }
+            Err(e) => {
+                self.consecutive_failures = self.consecutive_failures.saturating_add(1);
+                self.error_count = self.error_count.wrapping_add(1);
+                Err(e)
+            }
+        }
+    }
+
+    /// Pulse the reset line and re-initialise the controller if needed
+    ///
+    /// Call this after failed reads. Returns None if the failure threshold
+    /// isn't reached or all attempts are used up, otherwise the outcome of
+    /// the attempt.
+    pub fn recover<RST, D>(&mut self, reset: &mut RST, delay: &mut D) -> Option<RecoveryEvent>
+    where
+        RST: OutputPin,
+        D: DelayNs,
+    {
+        if !self.needs_recovery() {
+            return None;
+        }
+
+        self.recovery_attempts = self.recovery_attempts.saturating_add(1);
+        let attempt = self.recovery_attempts;
+
+        self.hardware_reset(
+            reset,
+            delay,
+            self.recovery.reset_low_ms,
+            self.recovery.boot_delay_ms,
+        );
+
+        if self.init().is_ok() {
+            self.consecutive_failures = 0;
+            self.recovery_attempts = 0;
+            self.recovery_count = self.recovery_count.saturating_add(1);
+            self.power_mode = PowerMode::Active;
+            self.touch_data.touch_num = 0;
+            // Velocities must not span the reset
+            self.motion.reset();
+            Some(RecoveryEvent::Recovered { attempt })
+        } else if self.recovery_exhausted() {
+            Some(RecoveryEvent::GaveUp)
+        } else {
+            Some(RecoveryEvent::Failed { attempt })
+        }
+    }
+}
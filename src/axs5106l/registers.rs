@@ -0,0 +1,347 @@
+//! Typed register map of the AXS5106L
+//!
+//! Each configuration register has a value type implementing
+//! [`RegisterValue`], so it can be read and written through
+//! [`Axs5106l::read_config`](super::Axs5106l::read_config),
+//! [`Axs5106l::write_config`](super::Axs5106l::write_config) and
+//! [`Axs5106l::modify_config`](super::Axs5106l::modify_config) instead of
+//! raw addresses and magic numbers. Values of read-only registers only
+//! implement [`FromRegister`], and raw writes take a [`WritableRegister`],
+//! so status registers can't be written by mistake.
+//!
+//! # Sources
+//!
+//! Only [`Register::TouchData`] (0x01), [`Register::ChipId`] (0x08) and the
+//! touch frame layout come from the vendor C++ driver this crate was ported
+//! from. All other addresses and their values follow the register layout
+//! common to FocalTech-style touch controllers and are **unverified** on
+//! the AXS5106L: neither the datasheet nor the vendor driver lists them.
+//! They are marked below; check them on a real panel (e.g. write and read
+//! back) before relying on them.
+
+use core::ops::BitOr;
+
+/// Register addresses of the AXS5106L
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Register {
+    /// Touch frame: gesture, point count and per-point data
+    TouchData = 0x01,
+    /// Firmware version (2 bytes, big-endian)
+    ///
+    /// Unverified, see the module docs.
+    FirmwareVersion = 0x05,
+    /// Device ID (3 bytes)
+    ChipId = 0x08,
+    /// Touch detection threshold (sensitivity)
+    ///
+    /// Unverified, see the module docs.
+    Threshold = 0x80,
+    /// Report rate in Hz while active
+    ///
+    /// Unverified, see the module docs.
+    ReportRate = 0x88,
+    /// Interrupt signalling mode
+    ///
+    /// Unverified, see the module docs.
+    InterruptMode = 0xA4,
+    /// Power mode
+    ///
+    /// Unverified, see the module docs.
+    PowerMode = 0xA5,
+    /// Gesture mode enable
+    ///
+    /// Unverified, see the module docs.
+    GestureEnable = 0xD0,
+    /// Enabled wake gestures mask
+    ///
+    /// Unverified, see the module docs.
+    GestureMask = 0xD1,
+    /// Detected gesture ID
+    ///
+    /// Unverified, see the module docs.
+    GestureId = 0xD3,
+    /// Firmware download control (enter, erase)
+    ///
+    /// Unverified like the other download registers, see the firmware
+    /// module docs.
+    #[cfg(feature = "unverified-fw-download")]
+    DownloadControl = 0xF0,
+    /// Firmware download status, bit 0 is set while flash is busy
+    #[cfg(feature = "unverified-fw-download")]
+    DownloadStatus = 0xF1,
+    /// Flash address for the next data access (3 bytes, big-endian)
+    #[cfg(feature = "unverified-fw-download")]
+    DownloadAddress = 0xF2,
+    /// Flash data window, auto-increments the download address
+    #[cfg(feature = "unverified-fw-download")]
+    DownloadData = 0xF3,
+}
+
+impl Register {
+    /// Get the register address
+    pub fn addr(self) -> u8 {
+        self as u8
+    }
+}
+
+/// Registers that accept writes
+///
+/// The subset of [`Register`] taken by
+/// [`Axs5106l::write_register`](super::Axs5106l::write_register).
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum WritableRegister {
+    Threshold,
+    ReportRate,
+    InterruptMode,
+    PowerMode,
+    GestureEnable,
+    GestureMask,
+    #[cfg(feature = "unverified-fw-download")]
+    DownloadControl,
+    #[cfg(feature = "unverified-fw-download")]
+    DownloadAddress,
+    #[cfg(feature = "unverified-fw-download")]
+    DownloadData,
+}
+
+impl WritableRegister {
+    /// Get the register address
+    pub fn addr(self) -> u8 {
+        Register::from(self).addr()
+    }
+}
+
+impl From<WritableRegister> for Register {
+    fn from(reg: WritableRegister) -> Self {
+        match reg {
+            WritableRegister::Threshold => Register::Threshold,
+            WritableRegister::ReportRate => Register::ReportRate,
+            WritableRegister::InterruptMode => Register::InterruptMode,
+            WritableRegister::PowerMode => Register::PowerMode,
+            WritableRegister::GestureEnable => Register::GestureEnable,
+            WritableRegister::GestureMask => Register::GestureMask,
+            #[cfg(feature = "unverified-fw-download")]
+            WritableRegister::DownloadControl => Register::DownloadControl,
+            #[cfg(feature = "unverified-fw-download")]
+            WritableRegister::DownloadAddress => Register::DownloadAddress,
+            #[cfg(feature = "unverified-fw-download")]
+            WritableRegister::DownloadData => Register::DownloadData,
+        }
+    }
+}
+
+/// A typed value read from a single register
+pub trait FromRegister: Sized {
+    /// Register holding the value
+    const REGISTER: Register;
+
+    /// Decode the register byte, None if the value is not documented
+    fn from_bits(bits: u8) -> Option<Self>;
+}
+
+/// A typed value stored in a single writable configuration register
+pub trait RegisterValue: FromRegister {
+    /// Register the value is written to, the same as
+    /// [`FromRegister::REGISTER`]
+    const WRITABLE: WritableRegister;
+
+    /// Encode the value into the register byte
+    fn to_bits(self) -> u8;
+}
+
+/// Power mode of the touch controller
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PowerMode {
+    /// Normal scanning and reporting
+    Active = 0x00,
+    /// Low-power scanning for wake gestures only
+    Gesture = 0x01,
+    /// Deep sleep, the controller doesn't answer on I2C until reset
+    Sleep = 0x03,
+}
+
+impl FromRegister for PowerMode {
+    const REGISTER: Register = Register::PowerMode;
+
+    fn from_bits(bits: u8) -> Option<Self> {
+        match bits {
+            0x00 => Some(PowerMode::Active),
+            0x01 => Some(PowerMode::Gesture),
+            0x03 => Some(PowerMode::Sleep),
+            _ => None,
+        }
+    }
+}
+
+impl RegisterValue for PowerMode {
+    const WRITABLE: WritableRegister = WritableRegister::PowerMode;
+
+    fn to_bits(self) -> u8 {
+        self as u8
+    }
+}
+
+/// How the controller drives the interrupt line
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum InterruptMode {
+    /// INT is held low for as long as a finger is on the panel
+    Level = 0x00,
+    /// INT is pulsed low once per new touch frame
+    Pulse = 0x01,
+}
+
+impl FromRegister for InterruptMode {
+    const REGISTER: Register = Register::InterruptMode;
+
+    fn from_bits(bits: u8) -> Option<Self> {
+        match bits {
+            0x00 => Some(InterruptMode::Level),
+            0x01 => Some(InterruptMode::Pulse),
+            _ => None,
+        }
+    }
+}
+
+impl RegisterValue for InterruptMode {
+    const WRITABLE: WritableRegister = WritableRegister::InterruptMode;
+
+    fn to_bits(self) -> u8 {
+        self as u8
+    }
+}
+
+/// Touch report rate in Hz
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct ReportRate(pub u8);
+
+impl FromRegister for ReportRate {
+    const REGISTER: Register = Register::ReportRate;
+
+    fn from_bits(bits: u8) -> Option<Self> {
+        Some(ReportRate(bits))
+    }
+}
+
+impl RegisterValue for ReportRate {
+    const WRITABLE: WritableRegister = WritableRegister::ReportRate;
+
+    fn to_bits(self) -> u8 {
+        self.0
+    }
+}
+
+/// Touch detection threshold, lower values are more sensitive
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Threshold(pub u8);
+
+impl FromRegister for Threshold {
+    const REGISTER: Register = Register::Threshold;
+
+    fn from_bits(bits: u8) -> Option<Self> {
+        Some(Threshold(bits))
+    }
+}
+
+impl RegisterValue for Threshold {
+    const WRITABLE: WritableRegister = WritableRegister::Threshold;
+
+    fn to_bits(self) -> u8 {
+        self.0
+    }
+}
+
+/// Gesture mode enable
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct GestureEnable(pub bool);
+
+impl FromRegister for GestureEnable {
+    const REGISTER: Register = Register::GestureEnable;
+
+    fn from_bits(bits: u8) -> Option<Self> {
+        match bits {
+            0x00 => Some(GestureEnable(false)),
+            0x01 => Some(GestureEnable(true)),
+            _ => None,
+        }
+    }
+}
+
+impl RegisterValue for GestureEnable {
+    const WRITABLE: WritableRegister = WritableRegister::GestureEnable;
+
+    fn to_bits(self) -> u8 {
+        self.0 as u8
+    }
+}
+
+/// Set of gestures that wake the controller from gesture mode
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct WakeGestures(u8);
+
+impl WakeGestures {
+    pub const NONE: Self = Self(0x00);
+    pub const DOUBLE_TAP: Self = Self(0x01);
+    pub const SWIPE_UP: Self = Self(0x02);
+    pub const SWIPE_DOWN: Self = Self(0x04);
+    pub const SWIPE_LEFT: Self = Self(0x08);
+    pub const SWIPE_RIGHT: Self = Self(0x10);
+
+    /// Get the raw register mask
+    pub fn bits(self) -> u8 {
+        self.0
+    }
+
+    /// Check if all gestures in `other` are enabled
+    pub fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+impl BitOr for WakeGestures {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl FromRegister for WakeGestures {
+    const REGISTER: Register = Register::GestureMask;
+
+    fn from_bits(bits: u8) -> Option<Self> {
+        Some(WakeGestures(bits & 0x1F))
+    }
+}
+
+impl RegisterValue for WakeGestures {
+    const WRITABLE: WritableRegister = WritableRegister::GestureMask;
+
+    fn to_bits(self) -> u8 {
+        self.0
+    }
+}
+
+/// Gesture reported by the controller while in gesture mode
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum WakeGesture {
+    DoubleTap,
+    SwipeUp,
+    SwipeDown,
+    SwipeLeft,
+    SwipeRight,
+}
+
+impl FromRegister for WakeGesture {
+    const REGISTER: Register = Register::GestureId;
+
+    fn from_bits(bits: u8) -> Option<Self> {
+        match bits {
+            0x20 => Some(WakeGesture::SwipeLeft),
+            0x21 => Some(WakeGesture::SwipeRight),
+            0x22 => Some(WakeGesture::SwipeUp),
+            0x23 => Some(WakeGesture::SwipeDown),
+            0x24 => Some(WakeGesture::DoubleTap),
+            _ => None,
+        }
+    }
+}
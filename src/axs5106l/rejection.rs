@@ -0,0 +1,105 @@
+//! Palm and large-contact rejection
+//!
+//! Drops touch frames with oversized contacts or too many simultaneous
+//! points, and holds back new fingers until they have been seen in a
+//! number of consecutive frames, so a palm resting along the side of the
+//! display or a single-frame glitch doesn't press anything.
+
+use super::{Coordinates, MAX_TOUCH_POINTS};
+
+/// Limits for rejecting touches
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct RejectionPolicy {
+    /// Largest contact area of a finger, 0 disables the check
+    pub max_area: u8,
+    /// Largest contact weight (pressure) of a finger, 0 disables the check
+    pub max_weight: u8,
+    /// Most simultaneous points, 0 disables the check
+    pub max_points: u8,
+    /// Consecutive frames a new finger must be seen in before it is reported
+    pub min_frames: u8,
+}
+
+impl Default for RejectionPolicy {
+    fn default() -> Self {
+        Self {
+            max_area: 0,
+            max_weight: 0,
+            max_points: 0,
+            min_frames: 1,
+        }
+    }
+}
+
+impl RejectionPolicy {
+    /// Check if a point is too large to be a finger
+    fn is_oversized(&self, point: &Coordinates) -> bool {
+        (self.max_area != 0 && point.area > self.max_area)
+            || (self.max_weight != 0 && point.weight > self.max_weight)
+    }
+}
+
+/// Rejection state carried between frames
+#[derive(Default)]
+pub(super) struct RejectionState {
+    /// A palm was detected, everything is rejected until the panel is clear
+    palm: bool,
+    /// Finger IDs and the number of consecutive frames they were seen in
+    seen: [Option<(u8, u8)>; MAX_TOUCH_POINTS],
+    /// Frames rejected since the driver was created
+    pub(super) rejected_frames: u32,
+}
+
+impl RejectionState {
+    /// Apply the policy to a frame of `count` points
+    ///
+    /// `reported` is the point count from the frame header, which can be
+    /// larger than the number of points the frame has room for. Rejected
+    /// points are removed and the remaining ones are moved to the front.
+    /// Returns the number of points kept.
+    pub(super) fn apply(
+        &mut self,
+        policy: RejectionPolicy,
+        points: &mut [Coordinates],
+        count: usize,
+        reported: usize,
+    ) -> usize {
+        if count == 0 {
+            self.palm = false;
+            self.seen = [None; MAX_TOUCH_POINTS];
+            return 0;
+        }
+
+        let too_many = policy.max_points != 0 && reported > policy.max_points as usize;
+        if self.palm || too_many || points[..count].iter().any(|p| policy.is_oversized(p)) {
+            self.palm = true;
+            self.seen = [None; MAX_TOUCH_POINTS];
+            self.rejected_frames = self.rejected_frames.wrapping_add(1);
+            return 0;
+        }
+
+        // Count how many consecutive frames every finger has been seen in
+        let mut seen = [None; MAX_TOUCH_POINTS];
+        for (slot, point) in seen.iter_mut().zip(points[..count].iter()) {
+            let frames = self
+                .seen
+                .iter()
+                .flatten()
+                .find(|&&(id, _)| id == point.id)
+                .map_or(1, |&(_, frames)| frames.saturating_add(1));
+            *slot = Some((point.id, frames));
+        }
+        self.seen = seen;
+
+        // Keep only confirmed fingers
+        let mut kept = 0;
+        for i in 0..count {
+            let confirmed = seen[i].is_some_and(|(_, frames)| frames >= policy.min_frames);
+            if confirmed {
+                points[kept] = points[i];
+                kept += 1;
+            }
+        }
+        kept
+    }
+}
@@ -8,20 +8,41 @@
 #[macro_use]
 extern crate alloc;
 
+use alloc::{boxed::Box, string::String};
+use core::cell::{Cell, RefCell};
+
+use critical_section::Mutex;
 use esp_backtrace as _;
 use esp_hal::clock::CpuClock;
 use esp_hal::ledc::channel::ChannelIFace;
 use esp_hal::ledc::timer::TimerIFace;
 use esp_hal::ledc::{LSGlobalClkSource, LowSpeed};
-use esp_hal::time::Duration;
+use esp_hal::time::{Duration, Instant};
 use esp_println::println;
 
-use display_test::axs5106l::{Axs5106l, Rotation};
+use display_test::axs5106l::{
+    Axs5106l, Axs5106lConfig, InterruptMode, RecoveryEvent, RejectionPolicy, Rotation, TouchEvent,
+    TouchEventQueue, TouchInterrupt, Transaction,
+};
+use display_test::diagnostics::{DiagnosticStats, Diagnostics};
+use display_test::display_interface::{DmaSpiInterface, MeteredInterface, TransferMeter};
+use display_test::framebuffer::TiledFramebuffer;
+use display_test::gesture::{GestureConfig, GestureDetector};
+use display_test::idle::{IdleAction, IdleConfig, IdleManager, IdleState};
+use display_test::qoi::Qoi;
+use display_test::ui::{
+    Button, Canvas, Chart, DrawError, Icon, Key, Keyboard, Label, Navigation, Navigator, Screen,
+    ScrollList, Slider, TextField, Theme, Toggle, Ui, Widget, WidgetEvent, WidgetId, set_theme,
+    theme,
+};
 
 use esp_hal::{
     analog::adc::{Adc, AdcConfig, Attenuation},
     delay::Delay,
-    gpio::{Input, InputConfig, Level, Output, OutputConfig, Pull},
+    dma::DmaTxBuf,
+    dma_buffers,
+    gpio::{Event, Input, InputConfig, Io, Level, Output, OutputConfig, Pull},
+    handler,
     i2c::master::I2c,
     ledc::Ledc,
     main,
@@ -37,27 +58,68 @@ use esp_hal::{
 
 // Display driver imports
 use embedded_graphics::{
-    mono_font::{MonoTextStyleBuilder, ascii::FONT_6X9},
     pixelcolor::Rgb565,
     prelude::*,
-    primitives::{Circle, Primitive, PrimitiveStyle, Triangle},
-    text::Text,
+    primitives::{Circle, Primitive, PrimitiveStyle, Rectangle, Triangle},
+    text::Alignment,
 };
 
-// Provides the parallel port and display interface builders
-use mipidsi::interface::SpiInterface;
-
 use mipidsi::options::Orientation;
 // Provides the Display builder
 use mipidsi::{Builder, models::ILI9341Rgb565, options::ColorInversion};
 
-use embedded_hal_bus::spi::ExclusiveDevice;
+use embedded_hal_bus::i2c::RefCellDevice;
 
 // Constants
 const VAL_TO_VOLT: f32 = 5.0 / 4096.0;
 const BACKLIGHT_DUTY: u8 = 80;
+const BACKLIGHT_DIM_DUTY: u8 = 10;
+const IDLE_DIM_AFTER_MS: u64 = 15_000;
+const IDLE_OFF_AFTER_MS: u64 = 45_000;
 const DISPLAY_WIDTH: u16 = 172;
 const DISPLAY_HEIGHT: u16 = 320;
+// Framebuffer tiles kept in the heap (2 KB each)
+const FRAMEBUFFER_TILES: usize = 12;
+// Size of each of the two display DMA buffers in bytes
+const DISPLAY_DMA_BUFFER: usize = 4092;
+// Read the touch controller after this long without an interrupt while a
+// finger is down, so a latched controller is noticed and recovered
+const TOUCH_POLL_MS: u64 = 100;
+// Interval between display throughput reports
+const DISPLAY_STATS_INTERVAL_MS: u64 = 5_000;
+// Lines kept on the gesture log page
+const LOG_LINES: usize = 40;
+// Longest device name
+const DEVICE_NAME_LEN: usize = 24;
+// Sensor chart history: 150 samples of 2 s, the last 5 minutes
+const CHART_SAMPLES: usize = 150;
+const CHART_INTERVAL_MS: u64 = 2_000;
+// Navigator pages, left to right
+const HOME_PAGE: usize = 0;
+const SENSOR_PAGE: usize = 1;
+const LOG_PAGE: usize = 3;
+
+/// Home page icons, converted with tools/png2qoi.py
+static BATTERY_ICON: &[u8] = include_bytes!("../../assets/battery.qoi");
+static THERMOMETER_ICON: &[u8] = include_bytes!("../../assets/thermometer.qoi");
+
+// Touch interrupt pin, owned by the GPIO interrupt handler
+static TOUCH_INT_PIN: Mutex<RefCell<Option<Input<'static>>>> = Mutex::new(RefCell::new(None));
+
+// Pending touch interrupt, handed from the GPIO interrupt handler to the driver
+static TOUCH_IRQ: TouchInterrupt = TouchInterrupt::new();
+
+// Touch events waiting to be handled by the main loop
+static TOUCH_EVENTS: TouchEventQueue<32> = TouchEventQueue::new();
+
+// Backlight set on the settings screen, applied by the main loop
+static BACKLIGHT: Mutex<Cell<BacklightSettings>> = Mutex::new(Cell::new(BacklightSettings {
+    duty: BACKLIGHT_DUTY,
+    locked: false,
+}));
+
+// Device name entered on the rename screen
+static DEVICE_NAME: Mutex<RefCell<String>> = Mutex::new(RefCell::new(String::new()));
 
 esp_bootloader_esp_idf::esp_app_desc!();
 
@@ -95,12 +157,10 @@ fn main() -> ! {
 
     // Initialize display control pins
     let cs = peripherals.GPIO14;
-    let mut cs_output = Output::new(cs, Level::High, OutputConfig::default());
     let mut rst = Output::new(peripherals.GPIO22, Level::Low, OutputConfig::default());
 
     // Perform display reset sequence
     println!("Reset display");
-    cs_output.set_low();
     delay.delay_millis(50);
     rst.set_low();
     delay.delay_millis(50);
@@ -152,16 +212,25 @@ fn main() -> ! {
     .unwrap()
     .with_sck(sclk)
     .with_miso(miso)
-    .with_mosi(mosi);
+    .with_mosi(mosi)
+    .with_cs(cs)
+    .with_dma(peripherals.DMA_CH0);
 
-    let spi_device = ExclusiveDevice::new_no_delay(spi, cs_output).unwrap();
+    // Two DMA buffers, one is filled while the other one is sent
+    let (_, _, front, front_descriptors) = dma_buffers!(0, DISPLAY_DMA_BUFFER);
+    let front_buffer = DmaTxBuf::new(front_descriptors, front).unwrap();
+    let (_, _, back, back_descriptors) = dma_buffers!(0, DISPLAY_DMA_BUFFER);
+    let back_buffer = DmaTxBuf::new(back_descriptors, back).unwrap();
 
     // ========================================
     // DISPLAY INITIALIZATION
     // ========================================
     println!("Initialize display driver");
-    let mut buffer = [0_u8; 512];
-    let di = SpiInterface::new(spi_device, dc, &mut buffer);
+    let display_meter = TransferMeter::new();
+    let di = MeteredInterface::new(
+        DmaSpiInterface::new(spi, dc, front_buffer, back_buffer).with_meter(&display_meter),
+        &display_meter,
+    );
 
     let mut display = Builder::new(ILI9341Rgb565, di)
         .reset_pin(rst)
@@ -173,9 +242,23 @@ fn main() -> ! {
         .init(&mut delay)
         .unwrap();
 
-    // Clear display and draw initial content
-    display.clear(Rgb565::BLACK).unwrap();
-    draw_smiley(&mut display).unwrap();
+    // Holding BOOT during start-up enters the touch diagnostics screen,
+    // pressing it afterwards switches between trails and heatmap
+    let boot_button = Input::new(
+        peripherals.GPIO9,
+        InputConfig::default().with_pull(Pull::Up),
+    );
+    let mut diagnostics = boot_button
+        .is_low()
+        .then(|| Diagnostics::new(DISPLAY_WIDTH, DISPLAY_HEIGHT));
+    let mut boot_was_pressed = boot_button.is_low();
+
+    // Clear display and draw initial content, the navigator draws its own
+    display.clear(theme().background).unwrap();
+    if let Some(diag) = diagnostics.as_mut() {
+        println!("Touch diagnostics mode");
+        diag.redraw(&mut display).unwrap();
+    }
 
     // ========================================
     // TOUCH DRIVER SETUP
@@ -195,14 +278,21 @@ fn main() -> ! {
     .with_scl(scl)
     .with_sda(sda);
 
+    // Share the bus, so other peripherals (RTC, sensors) can be added on GPIO18/19
+    let i2c_bus = RefCell::new(i2c);
+
     println!("Create touch driver instance");
     // Create touch driver instance
-    let mut touch = Axs5106l::new(
-        i2c,
-        Rotation::Rotate0, // Set display rotation
-        DISPLAY_WIDTH,     // Display width
-        DISPLAY_HEIGHT,    // Display height
-    );
+    let touch_config = Axs5106lConfig::new(DISPLAY_WIDTH, DISPLAY_HEIGHT)
+        .with_orientation(Rotation::Rotate0) // Set display rotation
+        .with_transaction(Transaction::Separate) // Same bus usage as the vendor driver
+        .with_interrupt_mode(InterruptMode::Pulse) // A falling edge for every touch frame
+        .with_rejection(RejectionPolicy {
+            max_points: 2, // A palm along the side shows up as extra points
+            min_frames: 2, // Ignore single-frame glitches
+            ..RejectionPolicy::default()
+        });
+    let mut touch = Axs5106l::with_config(RefCellDevice::new(&i2c_bus), &TOUCH_IRQ, touch_config);
 
     let mut touch_rst = Output::new(peripherals.GPIO20, Level::Low, OutputConfig::default());
     // Perform touch reset sequence
@@ -215,23 +305,30 @@ fn main() -> ! {
     // Initialize the touch controller
     touch.init().expect("Failed to initialize touch controller");
 
-    // Set up interrupt pin
-    let touch_int = Input::new(
+    // Set up interrupt pin, falling edges are captured by touch_interrupt_handler
+    let mut io = Io::new(peripherals.IO_MUX);
+    io.set_interrupt_handler(touch_interrupt_handler);
+
+    let mut touch_int = Input::new(
         peripherals.GPIO21,
         InputConfig::default().with_pull(Pull::Up),
     );
-    // Note: Not using hardware interrupts - polling the pin level instead
+    critical_section::with(|cs| {
+        touch_int.listen(Event::FallingEdge);
+        TOUCH_INT_PIN.borrow_ref_mut(cs).replace(touch_int);
+    });
 
     // ========================================
     // SENSOR SETUP
     // ========================================
 
-    // Setup text rendering style
-    let text_style = MonoTextStyleBuilder::new()
-        .font(&FONT_6X9)
-        .text_color(Rgb565::WHITE)
-        .background_color(Rgb565::BLACK)
-        .build();
+    // Screens are drawn into tiles and flushed in one go, so they don't tear
+    let mut framebuffer = TiledFramebuffer::new(
+        DISPLAY_WIDTH,
+        DISPLAY_HEIGHT,
+        FRAMEBUFFER_TILES,
+        theme().background,
+    );
 
     // Configure ADC for battery voltage monitoring
     let mut adc1_config = AdcConfig::new();
@@ -242,94 +339,617 @@ fn main() -> ! {
     let temperature_sensor =
         tsens::TemperatureSensor::new(peripherals.TSENS, tsens::Config::default()).unwrap();
 
+    // Swipe and edge-swipe detection on the touch events
+    let mut gestures = GestureDetector::new(GestureConfig::new(DISPLAY_WIDTH, DISPLAY_HEIGHT));
+
+    // ========================================
+    // SCREENS
+    // ========================================
+    // Home page with the sensors, swipe left for the sensor charts, info and
+    // gesture log pages. The settings screen is pushed from the home page.
+    let firmware = match touch.firmware_version() {
+        Ok(version) => format!("Touch FW: {:#06x}", version),
+        Err(_) => "Touch FW: unknown".into(),
+    };
+    let mut navigator = Navigator::new(DISPLAY_WIDTH, DISPLAY_HEIGHT);
+    navigator.add_page(HomeScreen::new());
+    let (sensor_screen, temperature_chart, vbat_chart) = sensor_screen();
+    navigator.add_page(sensor_screen);
+    navigator.add_page(info_screen(&firmware));
+    let (log_screen, log_list) = log_screen();
+    navigator.add_page(log_screen);
+    let mut backlight_duty = BACKLIGHT_DUTY;
+
+    // ========================================
+    // IDLE / BACKLIGHT MANAGEMENT
+    // ========================================
+    let mut idle = IdleManager::new(
+        IdleConfig {
+            dim_after_ms: IDLE_DIM_AFTER_MS,
+            off_after_ms: IDLE_OFF_AFTER_MS,
+            active_duty: BACKLIGHT_DUTY,
+            dim_duty: BACKLIGHT_DIM_DUTY,
+        },
+        Instant::now().duration_since_epoch().as_millis(),
+    );
+
     // ========================================
     // MAIN APPLICATION LOOP
     // ========================================
     let mut loop_count = 0;
+    let mut display_stats_ms = Instant::now().duration_since_epoch().as_millis();
+    let mut touch_read_ms = display_stats_ms;
     loop {
         delay.delay(Duration::from_millis(10));
         loop_count += 1;
-
-        // Poll the touch interrupt pin (active LOW)
-        if touch_int.is_low() {
-            println!("Touch interrupt pin is LOW - reading touch data");
-            touch.set_interrupt();
-        }
-
-        // Read touch data if interrupt flag is set in driver
-        if touch.has_interrupt() {
-            match touch.read_touch() {
+        let now_ms = Instant::now().duration_since_epoch().as_millis();
+        let mut idle_action = idle.update(now_ms);
+
+        // Read touch data if the interrupt handler signalled a falling edge,
+        // poll while a finger is down but the edges stopped
+        let queued = TOUCH_EVENTS.len();
+        let touch_result = if touch.has_interrupt() {
+            Some(touch.read_touch_events(&TOUCH_EVENTS))
+        } else if touch.has_touches() && now_ms - touch_read_ms >= TOUCH_POLL_MS {
+            Some(touch.poll_touch_events(&TOUCH_EVENTS))
+        } else {
+            None
+        };
+        if let Some(result) = touch_result {
+            touch_read_ms = now_ms;
+            match result {
                 Ok(_) => {
-                    // Get transformed coordinates
-                    if let Some(touch_data) = touch.get_coordinates() {
-                        for i in 0..touch_data.touch_num {
-                            let coord = touch_data.coords[i as usize];
-                            println!("Touch {}: x={}, y={}", i, coord.x, coord.y);
-                        }
+                    // A touch that wakes the screen is swallowed by the idle manager
+                    let (action, deliver) = idle.on_touch(now_ms, touch.has_touches());
+                    idle_action = action.or(idle_action);
+
+                    if !deliver {
+                        // Only this read's events, earlier fingers keep theirs
+                        TOUCH_EVENTS.truncate(queued);
                     }
                 }
                 Err(e) => {
-                    println!("Error reading touch data: {:?}", e);
+                    // Only report the first error of a streak, the recovery policy handles the rest
+                    if touch.consecutive_failures() == 1 {
+                        println!("Error reading touch data: {:?}", e);
+                    }
+                    match touch.recover(&mut touch_rst, &mut delay) {
+                        Some(RecoveryEvent::Recovered { attempt }) => {
+                            println!("Touch controller recovered (attempt {})", attempt);
+                        }
+                        Some(RecoveryEvent::Failed { attempt }) => {
+                            println!("Touch controller recovery attempt {} failed", attempt);
+                        }
+                        Some(RecoveryEvent::GaveUp) => {
+                            println!("Touch controller recovery gave up");
+                        }
+                        None => {}
+                    }
+                }
+            }
+            // Note: both reads take the pending interrupt
+        }
+
+        // Drain queued touch events
+        while let Some(event) = TOUCH_EVENTS.pop() {
+            if let Some(diag) = diagnostics.as_mut() {
+                if !display.is_sleeping() {
+                    diag.handle_event(&mut display, &event).unwrap();
+                }
+            } else {
+                navigator.handle_event(&event);
+            }
+            // Gestures go to the log page instead of the console
+            if let Some(gesture) = gestures.update(&event)
+                && let Some(list) = navigator
+                    .page_mut::<Ui>(LOG_PAGE)
+                    .and_then(|ui| ui.get_mut::<ScrollList>(log_list))
+            {
+                if list.len() >= LOG_LINES {
+                    list.remove(0);
                 }
+                list.push(&format!("{:?}", gesture));
+            }
+            if let TouchEvent::Up { id, .. } = event
+                && let Some(motion) = touch.motion(id)
+            {
+                // Lets a dragged list keep coasting
+                navigator.fling(id, &motion);
+            }
+        }
+
+        // Redraw what changed on the visible screens
+        if diagnostics.is_none() && !display.is_sleeping() {
+            navigator.update(now_ms);
+            let mut frame = framebuffer.frame(&mut display);
+            navigator.draw(&mut frame).unwrap();
+            frame.finish().unwrap();
+        }
+
+        // Apply backlight / display sleep changes from the idle manager
+        match idle_action {
+            Some(IdleAction::SetBacklight(duty)) => {
+                println!("Backlight duty {}%", duty);
+                channel0.set_duty(duty).unwrap();
+            }
+            Some(IdleAction::ScreenOff) => {
+                println!("Screen off");
+                channel0.set_duty(0).unwrap();
+                display.sleep(&mut delay).unwrap();
+            }
+            Some(IdleAction::WakeUp(duty)) => {
+                println!("Screen wake");
+                display.wake(&mut delay).unwrap();
+                channel0.set_duty(duty).unwrap();
             }
-            // Note: read_touch() already clears the interrupt flag internally
+            None => {}
+        }
+
+        // Apply a backlight change from the settings screen, after the idle
+        // action so a wake or undim from this loop can't restore the old level.
+        // Dimmed and off keep their level until the next touch restores it.
+        let settings = critical_section::with(|cs| BACKLIGHT.borrow(cs).get());
+        if settings.duty != backlight_duty {
+            backlight_duty = settings.duty;
+            idle.set_active_duty(backlight_duty);
+            if idle.state() == IdleState::Active {
+                channel0.set_duty(backlight_duty).unwrap();
+            }
+        }
+
+        // BOOT press switches the diagnostics screen between trails and heatmap
+        let boot_pressed = boot_button.is_low();
+        if boot_pressed
+            && !boot_was_pressed
+            && let Some(diag) = diagnostics.as_mut()
+        {
+            diag.toggle_mode(&mut display).unwrap();
+        }
+        boot_was_pressed = boot_pressed;
+
+        // Redraw the diagnostics after the screen comes back from sleep
+        if let (Some(IdleAction::WakeUp(_)), Some(diag)) = (idle_action, diagnostics.as_mut()) {
+            diag.redraw(&mut display).unwrap();
         }
 
         // Update display every 200 ms (10 times slower than loop frequency)
-        if loop_count >= 20 {
+        // Skipped while the display is asleep
+        if loop_count >= 20
+            && !display.is_sleeping()
+            && let Some(diag) = diagnostics.as_mut()
+        {
+            let stats = DiagnosticStats {
+                frames: touch.frame_count(),
+                i2c_errors: touch.error_count(),
+                recoveries: touch.recovery_count(),
+                rejected: touch.rejected_frames(),
+                overflows: TOUCH_EVENTS.overflow_count(),
+            };
+            diag.draw_status(
+                &mut display,
+                &stats,
+                Instant::now().duration_since_epoch().as_micros(),
+            )
+            .unwrap();
+            loop_count = 0;
+        } else if loop_count >= 20 && diagnostics.is_none() {
+            // Keeps sampling while the display sleeps, so the charts have no gaps
             // Read temperature sensor
             let temp = temperature_sensor.get_temperature();
-            let temp_str = format!("Temperature: {:.2} C", temp.to_celsius());
 
             // Read battery voltage via ADC
             let vbat_v: f32 = vbat_adc1.read_oneshot(&mut vbat_pin).unwrap() as f32 * VAL_TO_VOLT;
-            let volt_str = format!("VBAT ADC: {:.2} V", vbat_v);
 
-            // Update display with sensor readings
-            Text::new(volt_str.as_str(), Point::new(20, 30), text_style)
-                .draw(&mut display)
-                .unwrap();
-            Text::new(temp_str.as_str(), Point::new(20, 40), text_style)
-                .draw(&mut display)
-                .unwrap();
+            // Shown on the next navigator draw
+            if let Some(home) = navigator.page_mut::<HomeScreen>(HOME_PAGE) {
+                home.set_sensors(vbat_v, temp.to_celsius());
+            }
+            if let Some(ui) = navigator.page_mut::<Ui>(SENSOR_PAGE) {
+                let readings = [(temperature_chart, temp.to_celsius()), (vbat_chart, vbat_v)];
+                for (id, value) in readings {
+                    if let Some(chart) = ui.get_mut::<Chart<CHART_SAMPLES>>(id) {
+                        chart.push(now_ms, value);
+                    }
+                }
+            }
 
             loop_count = 0;
         }
+
+        // Report display throughput, to compare against the blocking SPI path
+        if now_ms.saturating_sub(display_stats_ms) >= DISPLAY_STATS_INTERVAL_MS {
+            let stats = display_meter.take();
+            println!(
+                "Display: {} bytes, {} DMA transfers, {} us in calls, {} us waiting, {} KB/s",
+                stats.bytes,
+                stats.transfers,
+                stats.call_us,
+                stats.wait_us,
+                stats.throughput_kbps()
+            );
+            display_stats_ms = now_ms;
+        }
     }
 }
 
+#[handler]
+fn touch_interrupt_handler() {
+    critical_section::with(|cs| {
+        if let Some(pin) = TOUCH_INT_PIN.borrow_ref_mut(cs).as_mut()
+            && pin.is_interrupt_set()
+        {
+            pin.clear_interrupt();
+            TOUCH_IRQ.signal(Instant::now().duration_since_epoch().as_micros());
+        }
+    });
+}
+
 fn draw_smiley<T>(display: &mut T) -> Result<(), T::Error>
 where
     T: DrawTarget<Color = Rgb565>,
 {
-    // Draw the left eye as a circle located at (50, 100), with a diameter of 40, filled with white
-    println!("draw_smiley");
+    let theme = theme();
+
+    // Draw the left eye as a circle located at (50, 100), with a diameter of 40, filled with the text color
     Circle::new(Point::new(50, 100), 40)
-        .into_styled(PrimitiveStyle::with_fill(Rgb565::WHITE))
+        .into_styled(PrimitiveStyle::with_fill(theme.text))
         .draw(display)?;
 
-    // Draw the right eye as a circle located at (50, 200), with a diameter of 40, filled with white
+    // Draw the right eye as a circle located at (50, 200), with a diameter of 40, filled with the text color
     Circle::new(Point::new(50, 200), 40)
-        .into_styled(PrimitiveStyle::with_fill(Rgb565::WHITE))
+        .into_styled(PrimitiveStyle::with_fill(theme.text))
         .draw(display)?;
 
-    // Draw an upside down red triangle to represent a smiling mouth
+    // Draw an upside down triangle in the brand color to represent a smiling mouth
     Triangle::new(
         Point::new(130, 140),
         Point::new(130, 200),
         Point::new(160, 170),
     )
-    .into_styled(PrimitiveStyle::with_fill(Rgb565::RED))
+    .into_styled(PrimitiveStyle::with_fill(theme.secondary))
     .draw(display)?;
 
-    // Cover the top part of the mouth with a black triangle so it looks closed instead of open
+    // Cover the top part of the mouth with a background triangle so it looks closed instead of open
     Triangle::new(
         Point::new(130, 150),
         Point::new(130, 190),
         Point::new(150, 170),
     )
-    .into_styled(PrimitiveStyle::with_fill(Rgb565::BLACK))
+    .into_styled(PrimitiveStyle::with_fill(theme.background))
     .draw(display)?;
 
     Ok(())
 }
+
+/// Backlight state shared between the settings screen and the main loop
+#[derive(Clone, Copy)]
+struct BacklightSettings {
+    duty: u8,
+    locked: bool,
+}
+
+/// First page: smiley, sensor readings and a button to the settings
+struct HomeScreen {
+    ui: Ui,
+    vbat: WidgetId,
+    temperature: WidgetId,
+    settings: WidgetId,
+    smiley_drawn: bool,
+}
+
+impl HomeScreen {
+    fn new() -> Self {
+        let mut ui = Ui::new().opaque();
+        // Icons and captions in the body font, values big enough to read
+        // from a distance, all above the smiley
+        let value = theme().large_font.scaled(2);
+        let icon = |data| Qoi::new(data).expect("icon is a valid QOI image");
+        ui.add(Icon::new(
+            Rectangle::new(Point::new(4, 12), Size::new(28, 28)),
+            icon(BATTERY_ICON),
+        ));
+        ui.add(Label::new(
+            Rectangle::new(Point::new(40, 4), Size::new(124, 14)),
+            "Battery",
+        ));
+        let vbat = ui.add(
+            Label::new(Rectangle::new(Point::new(40, 18), Size::new(124, 30)), "-")
+                .with_font(value),
+        );
+        ui.add(Icon::new(
+            Rectangle::new(Point::new(4, 58), Size::new(28, 28)),
+            icon(THERMOMETER_ICON),
+        ));
+        ui.add(Label::new(
+            Rectangle::new(Point::new(40, 50), Size::new(124, 14)),
+            "Temperature",
+        ));
+        let temperature = ui.add(
+            Label::new(Rectangle::new(Point::new(40, 64), Size::new(124, 30)), "-")
+                .with_font(value),
+        );
+        // Below the smiley
+        let settings = ui.add(Button::new(
+            Rectangle::new(Point::new(36, 264), Size::new(100, 32)),
+            "Settings",
+        ));
+        Self {
+            ui,
+            vbat,
+            temperature,
+            settings,
+            smiley_drawn: false,
+        }
+    }
+
+    /// Show new sensor readings
+    fn set_sensors(&mut self, vbat_v: f32, temperature_c: f32) {
+        if let Some(label) = self.ui.get_mut::<Label>(self.vbat) {
+            label.set_text(&format!("{:.2} V", vbat_v));
+        }
+        if let Some(label) = self.ui.get_mut::<Label>(self.temperature) {
+            label.set_text(&format!("{:.1} °C", temperature_c));
+        }
+    }
+}
+
+impl Screen for HomeScreen {
+    fn on_touch(&mut self, event: &TouchEvent) -> Option<Navigation> {
+        match self.ui.handle_event(event) {
+            Some((id, WidgetEvent::Clicked)) if id == self.settings => {
+                Some(Navigation::Push(Box::new(SettingsScreen::new())))
+            }
+            _ => None,
+        }
+    }
+
+    fn draw(&mut self, canvas: &mut Canvas<'_>) -> Result<(), DrawError> {
+        self.ui.paint(canvas)?;
+        // The smiley never changes, only draw it after a full redraw
+        if !self.smiley_drawn {
+            draw_smiley(canvas)?;
+            self.smiley_drawn = true;
+        }
+        Ok(())
+    }
+
+    fn invalidate(&mut self) {
+        self.ui.invalidate();
+        self.smiley_drawn = false;
+    }
+}
+
+/// Backlight brightness and lock, pushed on top of the home page
+struct SettingsScreen {
+    ui: Ui,
+    slider: WidgetId,
+    lock: WidgetId,
+    light: WidgetId,
+    rename: WidgetId,
+    back: WidgetId,
+}
+
+impl SettingsScreen {
+    fn new() -> Self {
+        let settings = critical_section::with(|cs| BACKLIGHT.borrow(cs).get());
+
+        let mut ui = Ui::new().opaque();
+        ui.add(
+            Label::new(
+                Rectangle::new(Point::new(0, 10), Size::new(DISPLAY_WIDTH as u32, 20)),
+                "Settings",
+            )
+            .with_alignment(Alignment::Center),
+        );
+        ui.add(Label::new(
+            Rectangle::new(Point::new(10, 44), Size::new(80, 16)),
+            "Backlight",
+        ));
+        let mut slider = Slider::new(
+            Rectangle::new(Point::new(10, 64), Size::new(152, 20)),
+            5,
+            100,
+            settings.duty as i32,
+        );
+        slider.base_mut().set_enabled(!settings.locked);
+        let slider = ui.add(slider);
+        ui.add(Label::new(
+            Rectangle::new(Point::new(10, 101), Size::new(80, 16)),
+            "Lock",
+        ));
+        let lock = ui.add(Toggle::new(
+            Rectangle::new(Point::new(122, 99), Size::new(40, 20)),
+            settings.locked,
+        ));
+        let rename = ui.add(Button::new(
+            Rectangle::new(Point::new(36, 140), Size::new(100, 32)),
+            "Device name",
+        ));
+        ui.add(Label::new(
+            Rectangle::new(Point::new(10, 193), Size::new(100, 16)),
+            "Light theme",
+        ));
+        let light = ui.add(Toggle::new(
+            Rectangle::new(Point::new(122, 191), Size::new(40, 20)),
+            theme().background == Theme::LIGHT.background,
+        ));
+        let back = ui.add(Button::new(
+            Rectangle::new(Point::new(36, 264), Size::new(100, 32)),
+            "Back",
+        ));
+        Self {
+            ui,
+            slider,
+            lock,
+            light,
+            rename,
+            back,
+        }
+    }
+}
+
+impl Screen for SettingsScreen {
+    fn on_touch(&mut self, event: &TouchEvent) -> Option<Navigation> {
+        let (id, widget_event) = self.ui.handle_event(event)?;
+        match widget_event {
+            WidgetEvent::Clicked if id == self.back => return Some(Navigation::Pop),
+            WidgetEvent::Toggled(light) if id == self.light => {
+                // The other screens repaint when they are shown again
+                set_theme(if light { Theme::LIGHT } else { Theme::DARK });
+                self.ui.invalidate();
+            }
+            WidgetEvent::Clicked if id == self.rename => {
+                return Some(Navigation::Push(Box::new(RenameScreen::new())));
+            }
+            WidgetEvent::Changed(duty) if id == self.slider => {
+                critical_section::with(|cs| {
+                    let cell = BACKLIGHT.borrow(cs);
+                    cell.set(BacklightSettings {
+                        duty: duty as u8,
+                        ..cell.get()
+                    });
+                });
+            }
+            WidgetEvent::Toggled(locked) if id == self.lock => {
+                // The toggle locks the backlight slider
+                if let Some(slider) = self.ui.get_mut::<Slider>(self.slider) {
+                    slider.base_mut().set_enabled(!locked);
+                }
+                critical_section::with(|cs| {
+                    let cell = BACKLIGHT.borrow(cs);
+                    cell.set(BacklightSettings {
+                        locked,
+                        ..cell.get()
+                    });
+                });
+            }
+            _ => {}
+        }
+        None
+    }
+
+    fn draw(&mut self, canvas: &mut Canvas<'_>) -> Result<(), DrawError> {
+        self.ui.paint(canvas)
+    }
+
+    fn invalidate(&mut self) {
+        self.ui.invalidate();
+    }
+}
+
+/// Device name entry with the on-screen keyboard, pushed from the settings
+struct RenameScreen {
+    ui: Ui,
+    field: WidgetId,
+}
+
+impl RenameScreen {
+    fn new() -> Self {
+        let name = critical_section::with(|cs| DEVICE_NAME.borrow_ref(cs).clone());
+
+        let mut ui = Ui::new().opaque();
+        ui.add(
+            Label::new(
+                Rectangle::new(Point::new(0, 10), Size::new(DISPLAY_WIDTH as u32, 20)),
+                "Device name",
+            )
+            .with_alignment(Alignment::Center),
+        );
+        let mut field = TextField::new(Rectangle::new(Point::new(6, 40), Size::new(160, 24)))
+            .with_placeholder("esp32c6-touch")
+            .with_max_len(DEVICE_NAME_LEN);
+        field.set_text(&name);
+        let field = ui.add(field);
+        ui.add(Keyboard::new(Rectangle::new(
+            Point::new(0, 200),
+            Size::new(DISPLAY_WIDTH as u32, 116),
+        )));
+        Self { ui, field }
+    }
+}
+
+impl Screen for RenameScreen {
+    fn on_touch(&mut self, event: &TouchEvent) -> Option<Navigation> {
+        let (_, WidgetEvent::Key(key)) = self.ui.handle_event(event)? else {
+            return None;
+        };
+        let field = self.ui.get_mut::<TextField>(self.field)?;
+        if key != Key::Enter {
+            field.key(key);
+            return None;
+        }
+
+        println!("Device name: {}", field.text());
+        critical_section::with(|cs| {
+            let mut name = DEVICE_NAME.borrow_ref_mut(cs);
+            name.clear();
+            name.push_str(field.text());
+        });
+        Some(Navigation::Pop)
+    }
+
+    fn update(&mut self, now_ms: u64) -> Option<Navigation> {
+        // Blinks the cursor
+        self.ui.update(now_ms);
+        None
+    }
+
+    fn draw(&mut self, canvas: &mut Canvas<'_>) -> Result<(), DrawError> {
+        self.ui.paint(canvas)
+    }
+
+    fn invalidate(&mut self) {
+        self.ui.invalidate();
+    }
+}
+
+/// Third page: display and touch controller details
+fn info_screen(firmware: &str) -> Ui {
+    let mut ui = Ui::new().opaque();
+    let lines = [
+        "About",
+        "Display: 172 x 320",
+        firmware,
+        "Swipe to change pages",
+    ];
+    for (i, line) in lines.into_iter().enumerate() {
+        ui.add(Label::new(
+            Rectangle::new(Point::new(10, 20 + i as i32 * 18), Size::new(152, 16)),
+            line,
+        ));
+    }
+    ui
+}
+
+/// Second page: temperature and battery voltage of the last minutes
+fn sensor_screen() -> (Ui, WidgetId, WidgetId) {
+    let mut ui = Ui::new().opaque();
+    let temperature = ui.add(
+        Chart::<CHART_SAMPLES>::new(Rectangle::new(Point::new(4, 10), Size::new(164, 140)))
+            .with_title("Temperature", "C")
+            .with_axes(true)
+            .with_interval_ms(CHART_INTERVAL_MS),
+    );
+    let vbat = ui.add(
+        Chart::<CHART_SAMPLES>::new(Rectangle::new(Point::new(4, 170), Size::new(164, 140)))
+            .with_title("VBAT", "V")
+            .with_axes(true)
+            .with_interval_ms(CHART_INTERVAL_MS)
+            .with_min_span(0.1),
+    );
+    (ui, temperature, vbat)
+}
+
+/// Fourth page: the latest gestures, in a list that can be flung
+fn log_screen() -> (Ui, WidgetId) {
+    let mut ui = Ui::new().opaque();
+    ui.add(Label::new(
+        Rectangle::new(Point::new(10, 10), Size::new(152, 20)),
+        "Gestures",
+    ));
+    let list = ui.add(ScrollList::new(
+        Rectangle::new(Point::new(0, 36), Size::new(DISPLAY_WIDTH as u32, 284)),
+        24,
+    ));
+    (ui, list)
+}
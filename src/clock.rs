@@ -0,0 +1,72 @@
+//! Monotonic time sources
+//!
+//! Code that needs timestamps takes a [`Clock`] instead of calling
+//! `esp_hal::time::Instant` directly, so it can be driven by
+//! [`ManualClock`] when running off-target or in tests.
+
+use core::cell::Cell;
+
+#[cfg(target_arch = "riscv32")]
+use esp_hal::time::Instant;
+
+/// Monotonic microsecond clock
+pub trait Clock {
+    /// Current time in microseconds since an arbitrary fixed point
+    fn now_us(&self) -> u64;
+
+    /// Current time in milliseconds
+    fn now_ms(&self) -> u64 {
+        self.now_us() / 1_000
+    }
+}
+
+impl<C: Clock> Clock for &C {
+    fn now_us(&self) -> u64 {
+        (**self).now_us()
+    }
+}
+
+/// System timer of the ESP32-C6 through `esp_hal::time::Instant`
+///
+/// Only a clock on the target, off-target code replaces it with
+/// `with_clock`.
+#[derive(Clone, Copy, Default)]
+pub struct SystemClock;
+
+#[cfg(target_arch = "riscv32")]
+impl Clock for SystemClock {
+    fn now_us(&self) -> u64 {
+        Instant::now().duration_since_epoch().as_micros()
+    }
+}
+
+/// Clock that only moves when told to
+#[derive(Default)]
+pub struct ManualClock {
+    now_us: Cell<u64>,
+}
+
+impl ManualClock {
+    /// Create a clock starting at `now_us`
+    pub const fn new(now_us: u64) -> Self {
+        Self {
+            now_us: Cell::new(now_us),
+        }
+    }
+
+    /// Set the current time
+    pub fn set(&self, now_us: u64) {
+        self.now_us.set(now_us);
+    }
+
+    /// Move the clock forward
+    pub fn advance(&self, us: u64) {
+        self.now_us.set(self.now_us.get() + us);
+    }
+}
+
+impl Clock for ManualClock {
+    fn now_us(&self) -> u64 {
+        self.now_us.get()
+    }
+}
@@ -0,0 +1,393 @@
+//! Touch diagnostics screen
+//!
+//! Draws each finger's trail in its own color, targets in the corners and
+//! edge midpoints that turn green once touched, a status block with the
+//! per-point coordinates, frame rate and I2C error counters, and optionally
+//! a heatmap of all touches. Production uses it to check that every part of
+//! a panel responds.
+
+use core::fmt::Write;
+
+use embedded_graphics::{
+    mono_font::{MonoTextStyle, MonoTextStyleBuilder, ascii::FONT_6X9},
+    pixelcolor::Rgb565,
+    prelude::*,
+    primitives::{Circle, Line, PrimitiveStyle, Rectangle},
+    text::{Baseline, Text},
+};
+
+use crate::axs5106l::TouchEvent;
+
+/// Number of fingers with their own trail
+const MAX_FINGERS: usize = 5;
+
+/// Trail colors, indexed by finger ID
+const FINGER_COLORS: [Rgb565; MAX_FINGERS] = [
+    Rgb565::RED,
+    Rgb565::GREEN,
+    Rgb565::CYAN,
+    Rgb565::YELLOW,
+    Rgb565::MAGENTA,
+];
+
+/// Size of a heatmap cell in pixels
+const HEATMAP_CELL: u16 = 8;
+
+/// Largest number of heatmap columns / rows (fits a 320x320 display)
+const HEATMAP_MAX_CELLS: usize = (320 / HEATMAP_CELL as usize) * (320 / HEATMAP_CELL as usize);
+
+/// Characters per status line (fits between the corner targets)
+const STATUS_COLUMNS: usize = 24;
+
+/// Distance from a target that counts as hitting it (pixels)
+const TARGET_RADIUS: i32 = 12;
+
+/// Counters shown in the status block
+#[derive(Clone, Copy, Default)]
+pub struct DiagnosticStats {
+    /// Touch frames read since start
+    pub frames: u32,
+    /// Failed touch reads since start
+    pub i2c_errors: u32,
+    /// Controller recoveries since start
+    pub recoveries: u32,
+    /// Frames dropped by the rejection policy
+    pub rejected: u32,
+    /// Events dropped because the event queue was full
+    pub overflows: u32,
+}
+
+/// What is drawn for touches
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum DiagnosticMode {
+    /// Per-finger trails
+    Trails,
+    /// Accumulated heatmap of all touches
+    Heatmap,
+}
+
+/// Touch diagnostics screen
+pub struct Diagnostics {
+    width: u16,
+    height: u16,
+    mode: DiagnosticMode,
+    last: [Option<Point>; MAX_FINGERS],
+    targets: [(Point, bool); 8],
+    heatmap: [u16; HEATMAP_MAX_CELLS],
+    last_frames: u32,
+    last_update_us: u64,
+    fps: u32,
+}
+
+impl Diagnostics {
+    /// Create a diagnostics screen for a display of `width` x `height` pixels
+    pub fn new(width: u16, height: u16) -> Self {
+        let w = width as i32 - 1;
+        let h = height as i32 - 1;
+        let targets = [
+            Point::new(0, 0),
+            Point::new(w / 2, 0),
+            Point::new(w, 0),
+            Point::new(w, h / 2),
+            Point::new(w, h),
+            Point::new(w / 2, h),
+            Point::new(0, h),
+            Point::new(0, h / 2),
+        ]
+        .map(|p| (p, false));
+
+        Self {
+            width,
+            height,
+            mode: DiagnosticMode::Trails,
+            last: [None; MAX_FINGERS],
+            targets,
+            heatmap: [0; HEATMAP_MAX_CELLS],
+            last_frames: 0,
+            last_update_us: 0,
+            fps: 0,
+        }
+    }
+
+    /// Get the current mode
+    pub fn mode(&self) -> DiagnosticMode {
+        self.mode
+    }
+
+    /// Switch between trails and heatmap and redraw the screen
+    pub fn toggle_mode<D>(&mut self, display: &mut D) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = Rgb565>,
+    {
+        self.mode = match self.mode {
+            DiagnosticMode::Trails => DiagnosticMode::Heatmap,
+            DiagnosticMode::Heatmap => DiagnosticMode::Trails,
+        };
+        self.redraw(display)
+    }
+
+    /// Clear the trails and heatmap and redraw the screen
+    pub fn reset<D>(&mut self, display: &mut D) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = Rgb565>,
+    {
+        self.heatmap = [0; HEATMAP_MAX_CELLS];
+        self.targets.iter_mut().for_each(|t| t.1 = false);
+        self.redraw(display)
+    }
+
+    /// Redraw the whole screen
+    ///
+    /// Trails are not kept, so they start over.
+    pub fn redraw<D>(&mut self, display: &mut D) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = Rgb565>,
+    {
+        display.clear(Rgb565::BLACK)?;
+        self.last = [None; MAX_FINGERS];
+
+        if self.mode == DiagnosticMode::Heatmap {
+            for row in 0..self.rows() {
+                for col in 0..self.columns() {
+                    self.draw_cell(display, col, row)?;
+                }
+            }
+        }
+
+        for i in 0..self.targets.len() {
+            self.draw_target(display, i)?;
+        }
+        Ok(())
+    }
+
+    /// Draw a touch event
+    pub fn handle_event<D>(&mut self, display: &mut D, event: &TouchEvent) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = Rgb565>,
+    {
+        let Some((x, y)) = event.position() else {
+            return Ok(());
+        };
+        let finger = event.id() as usize % MAX_FINGERS;
+        let point = Point::new(x as i32, y as i32);
+
+        // Targets
+        for i in 0..self.targets.len() {
+            let (target, hit) = self.targets[i];
+            let close = (target - point).x.abs() <= TARGET_RADIUS
+                && (target - point).y.abs() <= TARGET_RADIUS;
+            if close && !hit {
+                self.targets[i].1 = true;
+                self.draw_target(display, i)?;
+            }
+        }
+
+        match self.mode {
+            DiagnosticMode::Trails => {
+                let color = FINGER_COLORS[finger];
+                match (*event, self.last[finger]) {
+                    (TouchEvent::Move { .. } | TouchEvent::Up { .. }, Some(last)) => {
+                        Line::new(last, point)
+                            .into_styled(PrimitiveStyle::with_stroke(color, 2))
+                            .draw(display)?;
+                    }
+                    _ => {
+                        Circle::with_center(point, 7)
+                            .into_styled(PrimitiveStyle::with_stroke(color, 1))
+                            .draw(display)?;
+                    }
+                }
+            }
+            DiagnosticMode::Heatmap => {
+                let col = x / HEATMAP_CELL;
+                let row = y / HEATMAP_CELL;
+                if col < self.columns() && row < self.rows() {
+                    let index = self.cell_index(col, row);
+                    self.heatmap[index] = self.heatmap[index].saturating_add(1);
+                    self.draw_cell(display, col, row)?;
+                }
+            }
+        }
+
+        self.last[finger] = match event {
+            TouchEvent::Up { .. } => None,
+            _ => Some(point),
+        };
+        Ok(())
+    }
+
+    /// Draw the status block
+    ///
+    /// # Arguments
+    /// * `display` - Display to draw on
+    /// * `stats` - Driver and queue counters
+    /// * `now_us` - Current time, used for the frame rate
+    pub fn draw_status<D>(
+        &mut self,
+        display: &mut D,
+        stats: &DiagnosticStats,
+        now_us: u64,
+    ) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = Rgb565>,
+    {
+        let frames = stats.frames.wrapping_sub(self.last_frames) as u64;
+        let elapsed_us = now_us.saturating_sub(self.last_update_us);
+        if let Some(fps) = (frames * 1_000_000).checked_div(elapsed_us) {
+            self.fps = fps as u32;
+        }
+        self.last_frames = stats.frames;
+        self.last_update_us = now_us;
+
+        let style = status_style();
+        let mut line = StatusLine::new();
+
+        let _ = write!(line, "fps {:3}  frames {}", self.fps, stats.frames);
+        line.draw(display, 0, style)?;
+
+        let _ = write!(
+            line,
+            "i2c err {}  recov {}",
+            stats.i2c_errors, stats.recoveries
+        );
+        line.draw(display, 1, style)?;
+
+        let _ = write!(line, "reject {}  ovf {}", stats.rejected, stats.overflows);
+        line.draw(display, 2, style)?;
+
+        // Two fingers fit on a line
+        let mut active = self
+            .last
+            .iter()
+            .enumerate()
+            .filter_map(|(id, p)| Some((id, (*p)?)));
+        for row in 3..5 {
+            for (id, p) in active.by_ref().take(2) {
+                let _ = write!(line, "{}:{},{}  ", id, p.x, p.y);
+            }
+            line.draw(display, row, style)?;
+        }
+
+        let mode = match self.mode {
+            DiagnosticMode::Trails => "trails",
+            DiagnosticMode::Heatmap => "heatmap",
+        };
+        let hit = self.targets.iter().filter(|t| t.1).count();
+        let _ = write!(line, "{}  targets {}/{}", mode, hit, self.targets.len());
+        line.draw(display, 5, style)?;
+
+        Ok(())
+    }
+
+    fn columns(&self) -> u16 {
+        self.width.div_ceil(HEATMAP_CELL)
+    }
+
+    fn rows(&self) -> u16 {
+        self.height
+            .div_ceil(HEATMAP_CELL)
+            .min((HEATMAP_MAX_CELLS / self.columns().max(1) as usize) as u16)
+    }
+
+    fn cell_index(&self, col: u16, row: u16) -> usize {
+        row as usize * self.columns() as usize + col as usize
+    }
+
+    fn draw_cell<D>(&self, display: &mut D, col: u16, row: u16) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = Rgb565>,
+    {
+        let count = self.heatmap[self.cell_index(col, row)];
+        Rectangle::new(
+            Point::new((col * HEATMAP_CELL) as i32, (row * HEATMAP_CELL) as i32),
+            Size::new(HEATMAP_CELL as u32, HEATMAP_CELL as u32),
+        )
+        .into_styled(PrimitiveStyle::with_fill(heat_color(count)))
+        .draw(display)
+    }
+
+    fn draw_target<D>(&self, display: &mut D, index: usize) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = Rgb565>,
+    {
+        let (point, hit) = self.targets[index];
+        let color = if hit { Rgb565::GREEN } else { Rgb565::WHITE };
+        let style = if hit {
+            PrimitiveStyle::with_fill(color)
+        } else {
+            PrimitiveStyle::with_stroke(color, 1)
+        };
+        Circle::with_center(point, (TARGET_RADIUS * 2) as u32)
+            .into_styled(style)
+            .draw(display)?;
+        Ok(())
+    }
+}
+
+/// Color for a heatmap cell, from black over blue and green to red
+fn heat_color(count: u16) -> Rgb565 {
+    match count {
+        0 => Rgb565::BLACK,
+        1 => Rgb565::new(0, 0, 12),
+        2..=3 => Rgb565::BLUE,
+        4..=7 => Rgb565::CYAN,
+        8..=15 => Rgb565::GREEN,
+        16..=31 => Rgb565::YELLOW,
+        _ => Rgb565::RED,
+    }
+}
+
+fn status_style() -> MonoTextStyle<'static, Rgb565> {
+    MonoTextStyleBuilder::new()
+        .font(&FONT_6X9)
+        .text_color(Rgb565::WHITE)
+        .background_color(Rgb565::BLACK)
+        .build()
+}
+
+/// One line of status text, formatted without allocating
+struct StatusLine {
+    buffer: [u8; STATUS_COLUMNS],
+    len: usize,
+}
+
+impl StatusLine {
+    fn new() -> Self {
+        Self {
+            buffer: [b' '; STATUS_COLUMNS],
+            len: 0,
+        }
+    }
+
+    /// Draw the line padded to its full width, then clear it
+    fn draw<D>(
+        &mut self,
+        display: &mut D,
+        line: i32,
+        style: MonoTextStyle<'_, Rgb565>,
+    ) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = Rgb565>,
+    {
+        let y = 2 + line * style.font.character_size.height as i32;
+
+        // Padding overwrites what is left of a longer previous line
+        let text = core::str::from_utf8(&self.buffer).unwrap_or("");
+        Text::with_baseline(text, Point::new(TARGET_RADIUS + 2, y), style, Baseline::Top)
+            .draw(display)?;
+
+        self.buffer = [b' '; STATUS_COLUMNS];
+        self.len = 0;
+        Ok(())
+    }
+}
+
+impl Write for StatusLine {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        let bytes = s.as_bytes();
+        let n = bytes.len().min(self.buffer.len() - self.len);
+        self.buffer[self.len..self.len + n].copy_from_slice(&bytes[..n]);
+        self.len += n;
+        Ok(())
+    }
+}
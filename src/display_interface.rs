@@ -0,0 +1,333 @@
+//! DMA-backed SPI interface for the display
+//!
+//! [`DmaSpiInterface`] implements the mipidsi [`Interface`] on top of the
+//! esp-hal SPI DMA driver with two transmit buffers. While one buffer is
+//! being sent, the next pixels are packed into the other one, and the last
+//! buffer of a `set_pixels` call is left in flight, so the CPU can render
+//! the next tile while the previous one is still going out.
+//!
+//! [`MeteredInterface`] wraps any interface (DMA or the blocking
+//! `SpiInterface`) and counts bytes and the time the caller spends sending
+//! them, so both paths can be compared through the same [`TransferStats`].
+
+use core::cell::Cell;
+
+use embedded_hal::digital::OutputPin;
+use esp_hal::{
+    Blocking,
+    dma::DmaTxBuf,
+    spi::{
+        Error,
+        master::{SpiDma, SpiDmaTransfer},
+    },
+};
+use mipidsi::interface::Interface;
+
+use crate::clock::{Clock, SystemClock};
+
+/// Display transfer counters
+#[derive(Clone, Copy, Default, Debug)]
+pub struct TransferStats {
+    /// Bytes sent to the display (commands, parameters and pixels)
+    pub bytes: u64,
+    /// DMA transfers started (DMA interface only)
+    pub transfers: u32,
+    /// Wall time spent inside interface calls, waiting included (us)
+    pub call_us: u64,
+    /// Part of `call_us` spent waiting for a DMA transfer to finish (us)
+    pub wait_us: u64,
+}
+
+impl TransferStats {
+    /// Bytes sent per millisecond spent in interface calls, in KB/s
+    ///
+    /// With the blocking interface this is the SPI rate. With DMA, the
+    /// part of a transfer that overlaps drawing isn't counted, so the rate
+    /// is the one the drawing code sees.
+    pub fn throughput_kbps(&self) -> u32 {
+        (self.bytes * 1_000)
+            .checked_div(self.call_us)
+            .map_or(0, |kbps| kbps as u32)
+    }
+}
+
+/// Shared transfer counters
+///
+/// Created by the application and borrowed by the interface, so the
+/// counters stay readable after the interface has been moved into the
+/// display driver.
+#[derive(Default)]
+pub struct TransferMeter {
+    stats: Cell<TransferStats>,
+}
+
+impl TransferMeter {
+    /// Create a meter with all counters at zero
+    pub const fn new() -> Self {
+        Self {
+            stats: Cell::new(TransferStats {
+                bytes: 0,
+                transfers: 0,
+                call_us: 0,
+                wait_us: 0,
+            }),
+        }
+    }
+
+    /// Get the current counters
+    pub fn stats(&self) -> TransferStats {
+        self.stats.get()
+    }
+
+    /// Get the current counters and set them back to zero
+    pub fn take(&self) -> TransferStats {
+        self.stats.take()
+    }
+
+    fn update(&self, f: impl FnOnce(&mut TransferStats)) {
+        let mut stats = self.stats.get();
+        f(&mut stats);
+        self.stats.set(stats);
+    }
+}
+
+/// DMA interface error
+#[derive(Clone, Copy, Debug)]
+pub enum DmaSpiError<DC> {
+    /// SPI or DMA error
+    Spi(Error),
+    /// Data/command pin error
+    Dc(DC),
+}
+
+/// State of the SPI bus
+enum Bus<'d> {
+    Idle(SpiDma<'d, Blocking>),
+    Busy(SpiDmaTransfer<'d, Blocking, DmaTxBuf>),
+}
+
+/// Display interface sending through SPI DMA with two buffers
+pub struct DmaSpiInterface<'a, 'd, DC> {
+    bus: Option<Bus<'d>>,
+    /// Buffer being filled
+    back: DmaTxBuf,
+    /// Second buffer, `None` while it is being sent
+    spare: Option<DmaTxBuf>,
+    filled: usize,
+    dc: DC,
+    meter: Option<&'a TransferMeter>,
+}
+
+impl<'a, 'd, DC: OutputPin> DmaSpiInterface<'a, 'd, DC> {
+    /// Create a new interface
+    ///
+    /// # Arguments
+    /// * `spi` - SPI driver with a DMA channel (`Spi::with_dma`)
+    /// * `dc` - Data/command pin
+    /// * `front` / `back` - Transmit buffers in DMA-capable memory (`dma_buffers!`)
+    pub fn new(spi: SpiDma<'d, Blocking>, dc: DC, front: DmaTxBuf, back: DmaTxBuf) -> Self {
+        Self {
+            bus: Some(Bus::Idle(spi)),
+            back,
+            spare: Some(front),
+            filled: 0,
+            dc,
+            meter: None,
+        }
+    }
+
+    /// Record DMA transfers and waiting time in `meter`
+    pub fn with_meter(mut self, meter: &'a TransferMeter) -> Self {
+        self.meter = Some(meter);
+        self
+    }
+
+    /// Wait for the transfer in flight and return the SPI driver and pins
+    pub fn release(mut self) -> Result<(SpiDma<'d, Blocking>, DC), DmaSpiError<DC::Error>> {
+        self.flush()?;
+        let spi = self.wait();
+        Ok((spi, self.dc))
+    }
+
+    /// Send whatever is in the back buffer and wait until the bus is idle
+    pub fn flush(&mut self) -> Result<(), DmaSpiError<DC::Error>> {
+        self.submit()?;
+        let spi = self.wait();
+        self.bus = Some(Bus::Idle(spi));
+        Ok(())
+    }
+
+    /// Wait for the transfer in flight, if any
+    ///
+    /// Leaves `bus` empty; the caller puts the driver back.
+    fn wait(&mut self) -> SpiDma<'d, Blocking> {
+        match self.bus.take() {
+            Some(Bus::Idle(spi)) => spi,
+            Some(Bus::Busy(transfer)) => {
+                let start = SystemClock.now_us();
+                let (spi, buffer) = transfer.wait();
+                if let Some(meter) = self.meter {
+                    let waited = SystemClock.now_us().saturating_sub(start);
+                    meter.update(|s| s.wait_us += waited);
+                }
+                self.spare = Some(buffer);
+                spi
+            }
+            None => unreachable!("SPI bus lost after an earlier error"),
+        }
+    }
+
+    /// Start sending the back buffer and swap in the spare one
+    fn submit(&mut self) -> Result<(), DmaSpiError<DC::Error>> {
+        if self.filled == 0 {
+            return Ok(());
+        }
+
+        let spi = self.wait();
+        let spare = self.spare.take().expect("spare buffer is back once idle");
+        let mut buffer = core::mem::replace(&mut self.back, spare);
+        let len = core::mem::take(&mut self.filled);
+        buffer.set_length(len);
+
+        match spi.write(len, buffer) {
+            Ok(transfer) => {
+                self.bus = Some(Bus::Busy(transfer));
+                if let Some(meter) = self.meter {
+                    meter.update(|s| s.transfers += 1);
+                }
+                Ok(())
+            }
+            Err((e, spi, buffer)) => {
+                self.bus = Some(Bus::Idle(spi));
+                self.spare = Some(core::mem::replace(&mut self.back, buffer));
+                Err(DmaSpiError::Spi(e))
+            }
+        }
+    }
+
+    /// Append bytes to the back buffer, sending it whenever it fills up
+    fn push(&mut self, mut bytes: &[u8]) -> Result<(), DmaSpiError<DC::Error>> {
+        while !bytes.is_empty() {
+            let free = &mut self.back.as_mut_slice()[self.filled..];
+            let n = free.len().min(bytes.len());
+            free[..n].copy_from_slice(&bytes[..n]);
+            self.filled += n;
+            bytes = &bytes[n..];
+
+            if self.filled == self.back.capacity() {
+                self.submit()?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Switch the data/command pin once everything sent so far is out
+    fn set_dc(&mut self, data: bool) -> Result<(), DmaSpiError<DC::Error>> {
+        self.flush()?;
+        if data {
+            self.dc.set_high().map_err(DmaSpiError::Dc)
+        } else {
+            self.dc.set_low().map_err(DmaSpiError::Dc)
+        }
+    }
+}
+
+impl<DC: OutputPin> Interface for DmaSpiInterface<'_, '_, DC> {
+    type Word = u8;
+    type Error = DmaSpiError<DC::Error>;
+
+    fn send_command(&mut self, command: u8, args: &[u8]) -> Result<(), Self::Error> {
+        self.set_dc(false)?;
+        self.push(&[command])?;
+        self.set_dc(true)?;
+        self.push(args)?;
+        // Short parameter lists are sent right away, pixels may follow
+        self.submit()
+    }
+
+    fn send_pixels<const N: usize>(
+        &mut self,
+        pixels: impl IntoIterator<Item = [Self::Word; N]>,
+    ) -> Result<(), Self::Error> {
+        for pixel in pixels {
+            self.push(&pixel)?;
+        }
+        // The last chunk stays in flight while the caller renders on
+        self.submit()
+    }
+
+    fn send_repeated_pixel<const N: usize>(
+        &mut self,
+        pixel: [Self::Word; N],
+        count: u32,
+    ) -> Result<(), Self::Error> {
+        for _ in 0..count {
+            self.push(&pixel)?;
+        }
+        self.submit()
+    }
+}
+
+/// Interface wrapper counting bytes and CPU time
+pub struct MeteredInterface<'a, DI> {
+    di: DI,
+    meter: &'a TransferMeter,
+}
+
+impl<'a, DI: Interface> MeteredInterface<'a, DI> {
+    /// Wrap `di`, recording into `meter`
+    pub fn new(di: DI, meter: &'a TransferMeter) -> Self {
+        Self { di, meter }
+    }
+
+    /// Get the wrapped interface back
+    pub fn release(self) -> DI {
+        self.di
+    }
+
+    fn measure<T>(&mut self, f: impl FnOnce(&mut DI) -> (T, u64)) -> T {
+        let start = SystemClock.now_us();
+        let (result, bytes) = f(&mut self.di);
+        let elapsed = SystemClock.now_us().saturating_sub(start);
+        self.meter.update(|s| {
+            s.bytes += bytes;
+            s.call_us += elapsed;
+        });
+        result
+    }
+}
+
+impl<DI: Interface> Interface for MeteredInterface<'_, DI> {
+    type Word = DI::Word;
+    type Error = DI::Error;
+
+    fn send_command(&mut self, command: u8, args: &[u8]) -> Result<(), Self::Error> {
+        self.measure(|di| (di.send_command(command, args), 1 + args.len() as u64))
+    }
+
+    fn send_pixels<const N: usize>(
+        &mut self,
+        pixels: impl IntoIterator<Item = [Self::Word; N]>,
+    ) -> Result<(), Self::Error> {
+        let pixel_bytes = (N * core::mem::size_of::<DI::Word>()) as u64;
+        self.measure(|di| {
+            let mut count = 0u64;
+            let result = di.send_pixels(pixels.into_iter().inspect(|_| count += 1));
+            (result, count * pixel_bytes)
+        })
+    }
+
+    fn send_repeated_pixel<const N: usize>(
+        &mut self,
+        pixel: [Self::Word; N],
+        count: u32,
+    ) -> Result<(), Self::Error> {
+        let pixel_bytes = (N * core::mem::size_of::<DI::Word>()) as u64;
+        self.measure(|di| {
+            (
+                di.send_repeated_pixel(pixel, count),
+                count as u64 * pixel_bytes,
+            )
+        })
+    }
+}
@@ -0,0 +1,423 @@
+//! Fonts and text layout
+//!
+//! A [`Font`] is either an embedded-graphics `MonoFont` or a proportional
+//! [`BitmapFont`] compiled into flash, optionally scaled by a whole number
+//! for big readouts. [`TextBox`] lays text out inside a rectangle with
+//! alignment, word wrapping and an ellipsis when it doesn't fit:
+//!
+//! ```ignore
+//! let font = Font::bitmap(&FIXED_15_BOLD).scaled(2);
+//! TextBox::new("23.5 °C", area, font, Rgb565::WHITE)
+//!     .with_alignment(Alignment::Center)
+//!     .draw(&mut display)?;
+//! ```
+//!
+//! The proportional fonts are converted from the public domain misc-fixed
+//! BDF fonts by `tools/bdf2rust.py`, which also works for other BDF files.
+
+use alloc::vec::Vec;
+
+use embedded_graphics::{
+    mono_font::{MonoFont, MonoTextStyle},
+    pixelcolor::Rgb565,
+    prelude::*,
+    primitives::Rectangle,
+    text::{Alignment, Baseline, Text},
+};
+
+mod fixed_13;
+mod fixed_15_bold;
+
+pub use fixed_13::FIXED_13;
+pub use fixed_15_bold::FIXED_15_BOLD;
+
+/// Shown at the end of text that was cut off
+const ELLIPSIS: &str = "...";
+
+/// Character of a [`BitmapFont`]
+#[derive(Clone, Copy, Debug)]
+pub struct Glyph {
+    /// Character the glyph draws
+    pub c: char,
+    /// Distance to the next character
+    pub advance: u8,
+    /// Width of the ink
+    pub width: u8,
+    /// Height of the ink
+    pub height: u8,
+    /// Left edge of the ink from the pen position
+    pub left: i8,
+    /// Top edge of the ink from the baseline, negative above it
+    pub top: i8,
+    /// First bit of the glyph in [`BitmapFont::bitmap`]
+    pub offset: u32,
+}
+
+impl Glyph {
+    /// Create a glyph, used by the generated fonts
+    pub const fn new(
+        c: char,
+        advance: u8,
+        width: u8,
+        height: u8,
+        left: i8,
+        top: i8,
+        offset: u32,
+    ) -> Self {
+        Self {
+            c,
+            advance,
+            width,
+            height,
+            left,
+            top,
+            offset,
+        }
+    }
+
+    /// Whether the pixel at `x`, `y` of the ink is set
+    fn pixel(&self, bitmap: &[u8], x: u32, y: u32) -> bool {
+        let bit = self.offset + y * self.width as u32 + x;
+        bitmap[(bit / 8) as usize] & (0x80 >> (bit % 8)) != 0
+    }
+}
+
+/// Proportional font with 1 bit per pixel glyphs of varying width
+#[derive(Debug)]
+pub struct BitmapFont {
+    /// Height of the line above the baseline
+    pub ascent: u32,
+    /// Height of the line below the baseline
+    pub descent: u32,
+    /// Glyphs sorted by character
+    pub glyphs: &'static [Glyph],
+    /// Ink of all glyphs, row by row, most significant bit first
+    pub bitmap: &'static [u8],
+}
+
+impl BitmapFont {
+    /// Glyph of `c`, the one of `?` if the font doesn't have it
+    pub fn glyph(&self, c: char) -> &Glyph {
+        let find = |c: char| {
+            self.glyphs
+                .binary_search_by_key(&c, |glyph| glyph.c)
+                .ok()
+                .map(|i| &self.glyphs[i])
+        };
+        find(c).or_else(|| find('?')).unwrap_or(&self.glyphs[0])
+    }
+}
+
+/// Glyphs of a [`Font`]
+#[derive(Clone, Copy, Debug)]
+enum Face {
+    Mono(&'static MonoFont<'static>),
+    Bitmap(&'static BitmapFont),
+}
+
+/// Font and size to draw text with
+#[derive(Clone, Copy, Debug)]
+pub struct Font {
+    face: Face,
+    scale: u8,
+}
+
+impl Font {
+    /// Use an embedded-graphics monospaced font
+    pub const fn mono(font: &'static MonoFont<'static>) -> Self {
+        Self {
+            face: Face::Mono(font),
+            scale: 1,
+        }
+    }
+
+    /// Use a proportional font, e.g. [`FIXED_13`]
+    pub const fn bitmap(font: &'static BitmapFont) -> Self {
+        Self {
+            face: Face::Bitmap(font),
+            scale: 1,
+        }
+    }
+
+    /// Draw every pixel of the font as a `scale` by `scale` square
+    pub const fn scaled(mut self, scale: u8) -> Self {
+        self.scale = if scale == 0 { 1 } else { scale };
+        self
+    }
+
+    /// Distance between the tops of two lines
+    pub fn line_height(&self) -> u32 {
+        let height = match self.face {
+            Face::Mono(font) => font.character_size.height,
+            Face::Bitmap(font) => font.ascent + font.descent,
+        };
+        height * self.scale as u32
+    }
+
+    /// Distance from the top of a line to the baseline
+    pub fn ascent(&self) -> u32 {
+        let ascent = match self.face {
+            Face::Mono(font) => font.baseline,
+            Face::Bitmap(font) => font.ascent,
+        };
+        ascent * self.scale as u32
+    }
+
+    /// Distance from `c` to the next character
+    pub fn advance(&self, c: char) -> u32 {
+        let advance = match self.face {
+            Face::Mono(font) => font.character_size.width + font.character_spacing,
+            Face::Bitmap(font) => font.glyph(c).advance as u32,
+        };
+        advance * self.scale as u32
+    }
+
+    /// Width of `text` on one line
+    pub fn text_width(&self, text: &str) -> u32 {
+        text.chars().map(|c| self.advance(c)).sum()
+    }
+
+    /// Draw one line of text with the top left corner of the line at
+    /// `position`, without a background
+    pub fn draw<D>(
+        &self,
+        target: &mut D,
+        text: &str,
+        position: Point,
+        color: Rgb565,
+    ) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = Rgb565>,
+    {
+        let mut target = Scaled {
+            target,
+            origin: position,
+            scale: self.scale as i32,
+        };
+        match self.face {
+            Face::Mono(font) => {
+                let style = MonoTextStyle::new(font, color);
+                Text::with_baseline(text, position, style, Baseline::Top).draw(&mut target)?;
+            }
+            Face::Bitmap(font) => {
+                let mut pen = position.x;
+                let pixels = text.chars().flat_map(|c| {
+                    let glyph = font.glyph(c);
+                    let corner = Point::new(
+                        pen + glyph.left as i32,
+                        position.y + font.ascent as i32 + glyph.top as i32,
+                    );
+                    pen += glyph.advance as i32;
+                    let (width, height) = (glyph.width as u32, glyph.height as u32);
+                    (0..width * height)
+                        .map(move |i| (i % width, i / width))
+                        .filter(|&(x, y)| glyph.pixel(font.bitmap, x, y))
+                        .map(move |(x, y)| Pixel(corner + Point::new(x as i32, y as i32), color))
+                });
+                target.draw_iter(pixels)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Byte length of the longest start of `text` that fits in `width`
+    fn fit(&self, text: &str, width: u32) -> usize {
+        let mut used = 0;
+        for (i, c) in text.char_indices() {
+            used += self.advance(c);
+            if used > width {
+                return i;
+            }
+        }
+        text.len()
+    }
+}
+
+/// Draw target that scales everything drawn to it around `origin`
+struct Scaled<'a, D> {
+    target: &'a mut D,
+    origin: Point,
+    scale: i32,
+}
+
+impl<D: DrawTarget<Color = Rgb565>> Dimensions for Scaled<'_, D> {
+    fn bounding_box(&self) -> Rectangle {
+        self.target.bounding_box()
+    }
+}
+
+impl<D: DrawTarget<Color = Rgb565>> DrawTarget for Scaled<'_, D> {
+    type Color = Rgb565;
+    type Error = D::Error;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        if self.scale == 1 {
+            return self.target.draw_iter(pixels);
+        }
+        let size = Size::new_equal(self.scale as u32);
+        for Pixel(point, color) in pixels {
+            let corner = self.origin + (point - self.origin) * self.scale;
+            self.target
+                .fill_solid(&Rectangle::new(corner, size), color)?;
+        }
+        Ok(())
+    }
+}
+
+/// Vertical placement of the lines of a [`TextBox`]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum VerticalAlignment {
+    /// First line at the top
+    Top,
+    /// Lines centered
+    Middle,
+    /// Last line at the bottom
+    Bottom,
+}
+
+/// Line of a laid out [`TextBox`]
+struct Line<'a> {
+    text: &'a str,
+    /// Text was cut off after this line
+    ellipsis: bool,
+}
+
+/// Text laid out inside a rectangle
+///
+/// Without wrapping every line of the text (split at `\n`) is shown on
+/// its own and cut off with "..." when it's too wide. With wrapping, lines
+/// break between words, or inside a word wider than the box. Lines that
+/// don't fit the height are dropped and the last line shown ends in "...".
+/// Only the text is drawn, the caller paints the background.
+pub struct TextBox<'a> {
+    text: &'a str,
+    area: Rectangle,
+    font: Font,
+    color: Rgb565,
+    alignment: Alignment,
+    vertical_alignment: VerticalAlignment,
+    wrap: bool,
+}
+
+impl<'a> TextBox<'a> {
+    /// Create a left-aligned, vertically centered box that doesn't wrap
+    pub fn new(text: &'a str, area: Rectangle, font: Font, color: Rgb565) -> Self {
+        Self {
+            text,
+            area,
+            font,
+            color,
+            alignment: Alignment::Left,
+            vertical_alignment: VerticalAlignment::Middle,
+            wrap: false,
+        }
+    }
+
+    /// Set the horizontal alignment of every line
+    pub fn with_alignment(mut self, alignment: Alignment) -> Self {
+        self.alignment = alignment;
+        self
+    }
+
+    /// Set where the lines go when they don't fill the height
+    pub fn with_vertical_alignment(mut self, alignment: VerticalAlignment) -> Self {
+        self.vertical_alignment = alignment;
+        self
+    }
+
+    /// Break lines that are too wide instead of cutting them off
+    pub fn with_wrap(mut self, wrap: bool) -> Self {
+        self.wrap = wrap;
+        self
+    }
+
+    /// Split the text into the lines to draw
+    fn lines(&self) -> Vec<Line<'a>> {
+        let width = self.area.size.width;
+        let mut lines = Vec::new();
+        for paragraph in self.text.split('\n') {
+            let mut rest = paragraph;
+            loop {
+                let end = self.font.fit(rest, width);
+                if !self.wrap || end == rest.len() {
+                    lines.push(Line {
+                        text: rest,
+                        ellipsis: end < rest.len(),
+                    });
+                    break;
+                }
+                // Break at the last space that fits, inside the word if there
+                // is none, but always take at least one character
+                let end = if rest[end..].starts_with(' ') {
+                    end
+                } else {
+                    match rest[..end].rfind(' ') {
+                        Some(space) if space > 0 => space,
+                        _ => end.max(rest.chars().next().map_or(0, char::len_utf8)),
+                    }
+                };
+                lines.push(Line {
+                    text: &rest[..end],
+                    ellipsis: false,
+                });
+                rest = rest[end..].trim_start_matches(' ');
+            }
+        }
+
+        let max_lines = (self.area.size.height / self.font.line_height()).max(1) as usize;
+        if lines.len() > max_lines {
+            lines.truncate(max_lines);
+            lines[max_lines - 1].ellipsis = true;
+        }
+        for line in lines.iter_mut().filter(|line| line.ellipsis) {
+            let room = width.saturating_sub(self.font.text_width(ELLIPSIS));
+            line.text = line.text[..self.font.fit(line.text, room)].trim_end();
+        }
+        lines
+    }
+}
+
+impl Drawable for TextBox<'_> {
+    type Color = Rgb565;
+    type Output = ();
+
+    fn draw<D>(&self, target: &mut D) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = Rgb565>,
+    {
+        let lines = self.lines();
+        let line_height = self.font.line_height() as i32;
+        let height = lines.len() as i32 * line_height;
+        let free = self.area.size.height as i32 - height;
+        let mut y = self.area.top_left.y
+            + match self.vertical_alignment {
+                VerticalAlignment::Top => 0,
+                VerticalAlignment::Middle => free / 2,
+                VerticalAlignment::Bottom => free,
+            };
+
+        for line in lines {
+            let ellipsis = if line.ellipsis { ELLIPSIS } else { "" };
+            let width = self.font.text_width(line.text) + self.font.text_width(ellipsis);
+            let free = self.area.size.width as i32 - width as i32;
+            let x = self.area.top_left.x
+                + match self.alignment {
+                    Alignment::Left => 0,
+                    Alignment::Center => free / 2,
+                    Alignment::Right => free,
+                };
+            let position = Point::new(x, y);
+            self.font.draw(target, line.text, position, self.color)?;
+            if line.ellipsis {
+                let x = x + self.font.text_width(line.text) as i32;
+                self.font
+                    .draw(target, ELLIPSIS, Point::new(x, y), self.color)?;
+            }
+            y += line_height;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,139 @@
+//! Proportional version of the misc-fixed 6x13 font
+//!
+//! Generated by `tools/bdf2rust.py 6x13.bdf FIXED_13`, do not edit.
+//! The misc-fixed fonts are in the public domain.
+
+use super::{BitmapFont, Glyph};
+
+#[rustfmt::skip]
+pub const FIXED_13: BitmapFont = BitmapFont {
+    ascent: 11,
+    descent: 2,
+    glyphs: &[
+        Glyph::new(   ' ', 3, 0, 0, 0, 0, 0),
+        Glyph::new(   '!', 2, 1, 9, 0, -8, 0),
+        Glyph::new(   '"', 4, 3, 3, 0, -8, 9),
+        Glyph::new(   '#', 6, 5, 7, 0, -7, 18),
+        Glyph::new(   '$', 6, 5, 9, 0, -8, 53),
+        Glyph::new(   '%', 6, 5, 9, 0, -8, 98),
+        Glyph::new(   '&', 6, 5, 8, 0, -7, 143),
+        Glyph::new(  '\'', 2, 1, 3, 0, -8, 183),
+        Glyph::new(   '(', 4, 3, 11, 0, -9, 186),
+        Glyph::new(   ')', 4, 3, 11, 0, -9, 219),
+        Glyph::new(   '*', 6, 5, 5, 0, -8, 252),
+        Glyph::new(   '+', 6, 5, 5, 0, -6, 277),
+        Glyph::new(   ',', 4, 3, 3, 0, -1, 302),
+        Glyph::new(   '-', 6, 5, 1, 0, -4, 311),
+        Glyph::new(   '.', 4, 3, 3, 0, -1, 316),
+        Glyph::new(   '/', 6, 5, 9, 0, -8, 325),
+        Glyph::new(   '0', 6, 5, 9, 0, -8, 370),
+        Glyph::new(   '1', 6, 5, 9, 0, -8, 415),
+        Glyph::new(   '2', 6, 5, 9, 0, -8, 460),
+        Glyph::new(   '3', 6, 5, 9, 0, -8, 505),
+        Glyph::new(   '4', 6, 5, 9, 0, -8, 550),
+        Glyph::new(   '5', 6, 5, 9, 0, -8, 595),
+        Glyph::new(   '6', 6, 5, 9, 0, -8, 640),
+        Glyph::new(   '7', 6, 5, 9, 0, -8, 685),
+        Glyph::new(   '8', 6, 5, 9, 0, -8, 730),
+        Glyph::new(   '9', 6, 5, 9, 0, -8, 775),
+        Glyph::new(   ':', 4, 3, 8, 0, -6, 820),
+        Glyph::new(   ';', 4, 3, 8, 0, -6, 844),
+        Glyph::new(   '<', 6, 5, 9, 0, -8, 868),
+        Glyph::new(   '=', 6, 5, 4, 0, -5, 913),
+        Glyph::new(   '>', 6, 5, 9, 0, -8, 933),
+        Glyph::new(   '?', 6, 5, 9, 0, -8, 978),
+        Glyph::new(   '@', 6, 5, 9, 0, -8, 1023),
+        Glyph::new(   'A', 6, 5, 9, 0, -8, 1068),
+        Glyph::new(   'B', 6, 5, 9, 0, -8, 1113),
+        Glyph::new(   'C', 6, 5, 9, 0, -8, 1158),
+        Glyph::new(   'D', 6, 5, 9, 0, -8, 1203),
+        Glyph::new(   'E', 6, 5, 9, 0, -8, 1248),
+        Glyph::new(   'F', 6, 5, 9, 0, -8, 1293),
+        Glyph::new(   'G', 6, 5, 9, 0, -8, 1338),
+        Glyph::new(   'H', 6, 5, 9, 0, -8, 1383),
+        Glyph::new(   'I', 4, 3, 9, 0, -8, 1428),
+        Glyph::new(   'J', 6, 5, 9, 0, -8, 1455),
+        Glyph::new(   'K', 6, 5, 9, 0, -8, 1500),
+        Glyph::new(   'L', 6, 5, 9, 0, -8, 1545),
+        Glyph::new(   'M', 6, 5, 9, 0, -8, 1590),
+        Glyph::new(   'N', 6, 5, 9, 0, -8, 1635),
+        Glyph::new(   'O', 6, 5, 9, 0, -8, 1680),
+        Glyph::new(   'P', 6, 5, 9, 0, -8, 1725),
+        Glyph::new(   'Q', 6, 5, 10, 0, -8, 1770),
+        Glyph::new(   'R', 6, 5, 9, 0, -8, 1820),
+        Glyph::new(   'S', 6, 5, 9, 0, -8, 1865),
+        Glyph::new(   'T', 6, 5, 9, 0, -8, 1910),
+        Glyph::new(   'U', 6, 5, 9, 0, -8, 1955),
+        Glyph::new(   'V', 6, 5, 9, 0, -8, 2000),
+        Glyph::new(   'W', 6, 5, 9, 0, -8, 2045),
+        Glyph::new(   'X', 6, 5, 9, 0, -8, 2090),
+        Glyph::new(   'Y', 6, 5, 9, 0, -8, 2135),
+        Glyph::new(   'Z', 6, 5, 9, 0, -8, 2180),
+        Glyph::new(   '[', 4, 3, 11, 0, -9, 2225),
+        Glyph::new(  '\\', 6, 5, 9, 0, -8, 2258),
+        Glyph::new(   ']', 4, 3, 11, 0, -9, 2303),
+        Glyph::new(   '^', 6, 5, 3, 0, -8, 2336),
+        Glyph::new(   '_', 6, 5, 1, 0, 1, 2351),
+        Glyph::new(   '`', 3, 2, 2, 0, -9, 2356),
+        Glyph::new(   'a', 6, 5, 6, 0, -5, 2360),
+        Glyph::new(   'b', 6, 5, 9, 0, -8, 2390),
+        Glyph::new(   'c', 6, 5, 6, 0, -5, 2435),
+        Glyph::new(   'd', 6, 5, 9, 0, -8, 2465),
+        Glyph::new(   'e', 6, 5, 6, 0, -5, 2510),
+        Glyph::new(   'f', 6, 5, 9, 0, -8, 2540),
+        Glyph::new(   'g', 6, 5, 8, 0, -5, 2585),
+        Glyph::new(   'h', 6, 5, 9, 0, -8, 2625),
+        Glyph::new(   'i', 4, 3, 8, 0, -7, 2670),
+        Glyph::new(   'j', 5, 4, 10, 0, -7, 2694),
+        Glyph::new(   'k', 6, 5, 9, 0, -8, 2734),
+        Glyph::new(   'l', 4, 3, 9, 0, -8, 2779),
+        Glyph::new(   'm', 6, 5, 6, 0, -5, 2806),
+        Glyph::new(   'n', 6, 5, 6, 0, -5, 2836),
+        Glyph::new(   'o', 6, 5, 6, 0, -5, 2866),
+        Glyph::new(   'p', 6, 5, 8, 0, -5, 2896),
+        Glyph::new(   'q', 6, 5, 8, 0, -5, 2936),
+        Glyph::new(   'r', 6, 5, 6, 0, -5, 2976),
+        Glyph::new(   's', 6, 5, 6, 0, -5, 3006),
+        Glyph::new(   't', 6, 5, 8, 0, -7, 3036),
+        Glyph::new(   'u', 6, 5, 6, 0, -5, 3076),
+        Glyph::new(   'v', 6, 5, 6, 0, -5, 3106),
+        Glyph::new(   'w', 6, 5, 6, 0, -5, 3136),
+        Glyph::new(   'x', 6, 5, 6, 0, -5, 3166),
+        Glyph::new(   'y', 6, 5, 8, 0, -5, 3196),
+        Glyph::new(   'z', 6, 5, 6, 0, -5, 3236),
+        Glyph::new(   '{', 6, 5, 11, 0, -9, 3266),
+        Glyph::new(   '|', 2, 1, 9, 0, -8, 3321),
+        Glyph::new(   '}', 6, 5, 11, 0, -9, 3330),
+        Glyph::new(   '~', 6, 5, 3, 0, -8, 3385),
+        Glyph::new(   '°', 5, 4, 4, 0, -8, 3400),
+    ],
+    bitmap: &[
+        0xfe, 0xdb, 0x54, 0xaf, 0xab, 0xea, 0x51, 0x1f, 0x4a, 0x38, 0xa5, 0xf1, 0x13, 0x55, 0x08, 0x88,
+        0x55, 0x64, 0x8a, 0x51, 0x14, 0x9c, 0x9b, 0xca, 0x52, 0x49, 0x12, 0x31, 0x22, 0x49, 0x29, 0x42,
+        0x55, 0xd5, 0x21, 0x09, 0xf2, 0x11, 0xa9, 0xf5, 0xd0, 0x42, 0x21, 0x11, 0x08, 0x84, 0x08, 0xa8,
+        0xc6, 0x31, 0x8a, 0x88, 0x46, 0x50, 0x84, 0x21, 0x09, 0xf7, 0x46, 0x21, 0x11, 0x11, 0x0f, 0xfc,
+        0x22, 0x23, 0x82, 0x18, 0xb8, 0x42, 0x32, 0x95, 0x2f, 0x88, 0x5f, 0x84, 0x2d, 0x90, 0x86, 0x2e,
+        0x74, 0x61, 0x0f, 0x46, 0x31, 0x77, 0xc2, 0x21, 0x10, 0x88, 0x42, 0x1d, 0x18, 0xc5, 0xd1, 0x8c,
+        0x5c, 0xe8, 0xc6, 0x2f, 0x08, 0x62, 0xe5, 0xd0, 0x0b, 0xa5, 0xd0, 0x0d, 0x40, 0x88, 0x88, 0x82,
+        0x08, 0x20, 0xfc, 0x00, 0xfc, 0x10, 0x41, 0x04, 0x44, 0x44, 0x1d, 0x18, 0x84, 0x44, 0x20, 0x08,
+        0xe8, 0xc6, 0x75, 0xad, 0xa0, 0xf2, 0x2a, 0x31, 0x8f, 0xe3, 0x18, 0xf9, 0x29, 0x4b, 0x92, 0x94,
+        0xf9, 0xd1, 0x84, 0x21, 0x08, 0x45, 0xde, 0x4a, 0x52, 0x94, 0xa5, 0x3e, 0xfc, 0x21, 0x0f, 0x42,
+        0x10, 0xff, 0xe1, 0x08, 0x7a, 0x10, 0x84, 0x1d, 0x18, 0x42, 0x13, 0x8c, 0x5d, 0x18, 0xc6, 0x3f,
+        0x8c, 0x63, 0x1e, 0x92, 0x49, 0x2e, 0x71, 0x08, 0x42, 0x10, 0xa4, 0xc8, 0xc6, 0x54, 0xc5, 0x25,
+        0x18, 0xc2, 0x10, 0x84, 0x21, 0x08, 0x7e, 0x31, 0xdd, 0x6b, 0x18, 0xc6, 0x31, 0xce, 0x6b, 0x59,
+        0xce, 0x31, 0x74, 0x63, 0x18, 0xc6, 0x31, 0x77, 0xa3, 0x18, 0xfa, 0x10, 0x84, 0x1d, 0x18, 0xc6,
+        0x31, 0x8d, 0x5c, 0x1f, 0x46, 0x31, 0xf5, 0x25, 0x18, 0xba, 0x30, 0x83, 0x82, 0x18, 0xbb, 0xe4,
+        0x21, 0x08, 0x42, 0x10, 0x91, 0x8c, 0x63, 0x18, 0xc6, 0x2e, 0x8c, 0x63, 0x15, 0x29, 0x44, 0x24,
+        0x63, 0x18, 0xd6, 0xb5, 0xaa, 0xa3, 0x15, 0x28, 0x8a, 0x54, 0x63, 0x18, 0xa9, 0x44, 0x21, 0x08,
+        0x4f, 0x84, 0x42, 0x22, 0x11, 0x0f, 0xf9, 0x24, 0x92, 0x49, 0xe1, 0x04, 0x20, 0x82, 0x10, 0x43,
+        0xc9, 0x24, 0x92, 0x4f, 0x22, 0xa3, 0xf9, 0x70, 0x5f, 0x19, 0xb6, 0x10, 0x87, 0xa3, 0x18, 0xc7,
+        0xce, 0x8c, 0x21, 0x17, 0x04, 0x21, 0x7c, 0x63, 0x18, 0xbd, 0xd1, 0xfc, 0x22, 0xe3, 0x25, 0x08,
+        0xf2, 0x10, 0x84, 0x3a, 0x31, 0x8b, 0xc3, 0x17, 0x42, 0x10, 0xb6, 0x63, 0x18, 0xc5, 0x0c, 0x92,
+        0x5c, 0x40, 0xc4, 0x44, 0x66, 0x5a, 0x10, 0x84, 0xa9, 0x8a, 0x4a, 0x39, 0x24, 0x92, 0x5f, 0x55,
+        0xad, 0x6b, 0x1b, 0x66, 0x31, 0x8c, 0x5d, 0x18, 0xc6, 0x2e, 0xf4, 0x63, 0x1f, 0x42, 0x10, 0x7c,
+        0x63, 0x17, 0x84, 0x21, 0xb6, 0x61, 0x08, 0x41, 0xd1, 0x60, 0xa2, 0xe4, 0x23, 0xc8, 0x42, 0x12,
+        0x68, 0xc6, 0x31, 0x9b, 0x63, 0x18, 0xa9, 0x44, 0x8c, 0x6b, 0x5a, 0xaa, 0x2a, 0x21, 0x15, 0x18,
+        0xc6, 0x33, 0x68, 0x62, 0xef, 0x88, 0x88, 0x87, 0xc6, 0x42, 0x10, 0x98, 0x21, 0x08, 0x41, 0xff,
+        0xf0, 0x42, 0x10, 0x83, 0x21, 0x08, 0x4c, 0x26, 0xb2, 0x69, 0x96,
+    ],
+};
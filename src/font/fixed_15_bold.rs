@@ -0,0 +1,159 @@
+//! Proportional version of the misc-fixed 9x15B font
+//!
+//! Generated by `tools/bdf2rust.py 9x15B.bdf FIXED_15_BOLD`, do not edit.
+//! The misc-fixed fonts are in the public domain.
+
+use super::{BitmapFont, Glyph};
+
+#[rustfmt::skip]
+pub const FIXED_15_BOLD: BitmapFont = BitmapFont {
+    ascent: 12,
+    descent: 3,
+    glyphs: &[
+        Glyph::new(   ' ', 4, 0, 0, 0, 0, 0),
+        Glyph::new(   '!', 3, 2, 10, 0, -9, 0),
+        Glyph::new(   '"', 6, 5, 3, 0, -9, 20),
+        Glyph::new(   '#', 8, 7, 8, 0, -8, 35),
+        Glyph::new(   '$', 8, 7, 11, 0, -9, 91),
+        Glyph::new(   '%', 9, 8, 10, 0, -9, 168),
+        Glyph::new(   '&', 9, 8, 10, 0, -9, 248),
+        Glyph::new(  '\'', 3, 2, 4, 0, -9, 328),
+        Glyph::new(   '(', 6, 5, 12, 0, -10, 336),
+        Glyph::new(   ')', 6, 5, 12, 0, -10, 396),
+        Glyph::new(   '*', 8, 7, 5, 0, -6, 456),
+        Glyph::new(   '+', 9, 8, 7, 0, -7, 491),
+        Glyph::new(   ',', 4, 3, 5, 0, -1, 547),
+        Glyph::new(   '-', 9, 8, 1, 0, -4, 562),
+        Glyph::new(   '.', 5, 4, 3, 0, -1, 570),
+        Glyph::new(   '/', 9, 8, 10, 0, -9, 582),
+        Glyph::new(   '0', 9, 8, 10, 0, -9, 662),
+        Glyph::new(   '1', 9, 6, 10, 1, -9, 742),
+        Glyph::new(   '2', 9, 8, 10, 0, -9, 802),
+        Glyph::new(   '3', 9, 8, 10, 0, -9, 882),
+        Glyph::new(   '4', 9, 8, 10, 0, -9, 962),
+        Glyph::new(   '5', 9, 8, 10, 0, -9, 1042),
+        Glyph::new(   '6', 9, 8, 10, 0, -9, 1122),
+        Glyph::new(   '7', 9, 8, 10, 0, -9, 1202),
+        Glyph::new(   '8', 9, 8, 10, 0, -9, 1282),
+        Glyph::new(   '9', 9, 8, 10, 0, -9, 1362),
+        Glyph::new(   ':', 5, 4, 8, 0, -6, 1442),
+        Glyph::new(   ';', 5, 4, 10, 0, -6, 1474),
+        Glyph::new(   '<', 7, 6, 10, 0, -9, 1514),
+        Glyph::new(   '=', 9, 8, 4, 0, -5, 1574),
+        Glyph::new(   '>', 7, 6, 10, 0, -9, 1606),
+        Glyph::new(   '?', 8, 7, 10, 0, -9, 1666),
+        Glyph::new(   '@', 9, 8, 10, 0, -9, 1736),
+        Glyph::new(   'A', 9, 8, 10, 0, -9, 1816),
+        Glyph::new(   'B', 9, 8, 10, 0, -9, 1896),
+        Glyph::new(   'C', 9, 8, 10, 0, -9, 1976),
+        Glyph::new(   'D', 9, 8, 10, 0, -9, 2056),
+        Glyph::new(   'E', 8, 7, 10, 0, -9, 2136),
+        Glyph::new(   'F', 9, 8, 10, 0, -9, 2206),
+        Glyph::new(   'G', 9, 8, 10, 0, -9, 2286),
+        Glyph::new(   'H', 9, 8, 10, 0, -9, 2366),
+        Glyph::new(   'I', 7, 6, 10, 0, -9, 2446),
+        Glyph::new(   'J', 7, 6, 10, 0, -9, 2506),
+        Glyph::new(   'K', 9, 8, 10, 0, -9, 2566),
+        Glyph::new(   'L', 8, 7, 10, 0, -9, 2646),
+        Glyph::new(   'M', 9, 8, 10, 0, -9, 2716),
+        Glyph::new(   'N', 9, 8, 10, 0, -9, 2796),
+        Glyph::new(   'O', 9, 8, 10, 0, -9, 2876),
+        Glyph::new(   'P', 9, 8, 10, 0, -9, 2956),
+        Glyph::new(   'Q', 9, 8, 10, 0, -9, 3036),
+        Glyph::new(   'R', 9, 8, 10, 0, -9, 3116),
+        Glyph::new(   'S', 9, 8, 10, 0, -9, 3196),
+        Glyph::new(   'T', 9, 8, 10, 0, -9, 3276),
+        Glyph::new(   'U', 9, 8, 10, 0, -9, 3356),
+        Glyph::new(   'V', 9, 8, 10, 0, -9, 3436),
+        Glyph::new(   'W', 9, 8, 10, 0, -9, 3516),
+        Glyph::new(   'X', 9, 8, 10, 0, -9, 3596),
+        Glyph::new(   'Y', 9, 8, 10, 0, -9, 3676),
+        Glyph::new(   'Z', 8, 7, 10, 0, -9, 3756),
+        Glyph::new(   '[', 6, 5, 12, 0, -10, 3826),
+        Glyph::new(  '\\', 9, 8, 10, 0, -9, 3886),
+        Glyph::new(   ']', 6, 5, 12, 0, -10, 3966),
+        Glyph::new(   '^', 7, 6, 4, 0, -9, 4026),
+        Glyph::new(   '_', 9, 8, 1, 0, 1, 4050),
+        Glyph::new(   '`', 5, 4, 3, 0, -10, 4058),
+        Glyph::new(   'a', 9, 8, 7, 0, -6, 4070),
+        Glyph::new(   'b', 9, 8, 10, 0, -9, 4126),
+        Glyph::new(   'c', 9, 8, 7, 0, -6, 4206),
+        Glyph::new(   'd', 9, 8, 10, 0, -9, 4262),
+        Glyph::new(   'e', 9, 8, 7, 0, -6, 4342),
+        Glyph::new(   'f', 9, 8, 10, 0, -9, 4398),
+        Glyph::new(   'g', 9, 8, 10, 0, -6, 4478),
+        Glyph::new(   'h', 9, 8, 10, 0, -9, 4558),
+        Glyph::new(   'i', 7, 6, 10, 0, -9, 4638),
+        Glyph::new(   'j', 8, 7, 13, 0, -9, 4698),
+        Glyph::new(   'k', 8, 7, 10, 0, -9, 4789),
+        Glyph::new(   'l', 7, 6, 10, 0, -9, 4859),
+        Glyph::new(   'm', 9, 8, 7, 0, -6, 4919),
+        Glyph::new(   'n', 9, 8, 7, 0, -6, 4975),
+        Glyph::new(   'o', 9, 8, 7, 0, -6, 5031),
+        Glyph::new(   'p', 9, 8, 10, 0, -6, 5087),
+        Glyph::new(   'q', 9, 8, 10, 0, -6, 5167),
+        Glyph::new(   'r', 9, 8, 7, 0, -6, 5247),
+        Glyph::new(   's', 9, 8, 7, 0, -6, 5303),
+        Glyph::new(   't', 9, 8, 9, 0, -8, 5359),
+        Glyph::new(   'u', 9, 8, 7, 0, -6, 5431),
+        Glyph::new(   'v', 9, 8, 7, 0, -6, 5487),
+        Glyph::new(   'w', 9, 8, 7, 0, -6, 5543),
+        Glyph::new(   'x', 9, 8, 7, 0, -6, 5599),
+        Glyph::new(   'y', 9, 8, 10, 0, -6, 5655),
+        Glyph::new(   'z', 7, 6, 7, 0, -6, 5735),
+        Glyph::new(   '{', 6, 5, 12, 0, -10, 5777),
+        Glyph::new(   '|', 3, 2, 10, 0, -9, 5837),
+        Glyph::new(   '}', 6, 5, 12, 0, -10, 5857),
+        Glyph::new(   '~', 9, 8, 3, 0, -9, 5917),
+        Glyph::new(   '°', 7, 6, 4, 0, -9, 5941),
+    ],
+    bitmap: &[
+        0xff, 0xff, 0x3d, 0xef, 0x6d, 0x9b, 0x7f, 0x6c, 0xdb, 0xfb, 0x66, 0xc2, 0x1f, 0x6b, 0xd1, 0xe1,
+        0xf0, 0xf1, 0x7a, 0xdf, 0x08, 0x43, 0xe6, 0xe6, 0x4c, 0x18, 0x18, 0x32, 0x67, 0x67, 0xc2, 0x38,
+        0x6c, 0x6c, 0x78, 0x30, 0x79, 0xcf, 0xc6, 0xce, 0x7b, 0xff, 0x19, 0x98, 0xcc, 0x63, 0x18, 0x63,
+        0x0c, 0x3c, 0x30, 0xc6, 0x18, 0xc6, 0x33, 0x19, 0x98, 0x6c, 0x73, 0xf9, 0xc6, 0xc3, 0x03, 0x03,
+        0x1f, 0xe3, 0x03, 0x03, 0x1f, 0xb7, 0xbf, 0xdb, 0xd8, 0x0c, 0x18, 0x18, 0x30, 0x60, 0x60, 0xc1,
+        0x81, 0x83, 0x00, 0x60, 0xf1, 0x9b, 0x0f, 0x0f, 0x0f, 0x0d, 0x98, 0xf0, 0x60, 0xc7, 0x3c, 0x30,
+        0xc3, 0x0c, 0x30, 0xcf, 0xcf, 0x19, 0xb0, 0xc0, 0xc1, 0x83, 0x06, 0x0c, 0x18, 0x3f, 0xdf, 0x31,
+        0x80, 0xc1, 0x87, 0x01, 0x80, 0xc0, 0xf1, 0x9f, 0x01, 0x83, 0x87, 0x8d, 0x99, 0xb1, 0xbf, 0xc1,
+        0x81, 0x81, 0xbf, 0xb0, 0x30, 0x37, 0x39, 0x80, 0xc0, 0xf0, 0xd9, 0x8f, 0x0f, 0x19, 0xb0, 0xb0,
+        0x37, 0x39, 0xb0, 0xf0, 0xd9, 0x8f, 0x3f, 0xc0, 0xc0, 0xc1, 0x83, 0x03, 0x06, 0x06, 0x06, 0x06,
+        0x0f, 0x19, 0xb0, 0xd9, 0x8f, 0x19, 0xb0, 0xf0, 0xd9, 0x8f, 0x0f, 0x19, 0xb0, 0xf0, 0xd9, 0xce,
+        0xc0, 0xd0, 0xd9, 0x8f, 0x1b, 0xd8, 0x01, 0xbd, 0x9b, 0xd8, 0x01, 0xdc, 0xcd, 0x83, 0x18, 0xc6,
+        0x30, 0xc1, 0x83, 0x06, 0x0f, 0xfc, 0x00, 0x03, 0xff, 0x06, 0x0c, 0x18, 0x30, 0xc6, 0x31, 0x8c,
+        0x1f, 0x63, 0xc6, 0x0c, 0x30, 0xc3, 0x00, 0x0c, 0x18, 0x3c, 0x66, 0xc3, 0xcf, 0xdb, 0xdb, 0xce,
+        0xc0, 0x63, 0x3e, 0x18, 0x3c, 0x66, 0xc3, 0xc3, 0xc3, 0xff, 0xc3, 0xc3, 0xc3, 0xfc, 0xc6, 0xc3,
+        0xc6, 0xfc, 0xc6, 0xc3, 0xc3, 0xc6, 0xfc, 0x3e, 0x63, 0xc1, 0xc0, 0xc0, 0xc0, 0xc0, 0xc1, 0x63,
+        0x3e, 0xfc, 0xc6, 0xc3, 0xc3, 0xc3, 0xc3, 0xc3, 0xc3, 0xc6, 0xfc, 0xff, 0x83, 0x06, 0x0f, 0xd8,
+        0x30, 0x60, 0xc1, 0xff, 0xff, 0x03, 0x03, 0x03, 0xf3, 0x03, 0x03, 0x03, 0x03, 0x00, 0xf9, 0x8f,
+        0x03, 0x03, 0x03, 0x1f, 0x0f, 0x0d, 0x8c, 0xfb, 0x0f, 0x0f, 0x0f, 0x0f, 0xff, 0x0f, 0x0f, 0x0f,
+        0x0f, 0x0f, 0xf3, 0x0c, 0x30, 0xc3, 0x0c, 0x30, 0xcf, 0xcf, 0x0c, 0x30, 0xc3, 0x0c, 0x38, 0xf6,
+        0x73, 0x0f, 0x1b, 0x33, 0x63, 0xc3, 0xc3, 0x63, 0x33, 0x1b, 0x0f, 0x06, 0x0c, 0x18, 0x30, 0x60,
+        0xc1, 0x83, 0x07, 0xfc, 0x3e, 0x7f, 0xfd, 0xbd, 0xbd, 0xbc, 0x3c, 0x3c, 0x3c, 0x3c, 0x3e, 0x3f,
+        0x3f, 0x3d, 0xbd, 0xbc, 0xfc, 0x7c, 0x7c, 0x33, 0xc6, 0x6c, 0x3c, 0x3c, 0x3c, 0x3c, 0x3c, 0x36,
+        0x63, 0xcf, 0xec, 0x3c, 0x3c, 0x3f, 0xec, 0x0c, 0x0c, 0x0c, 0x0c, 0x03, 0xc6, 0x6c, 0x3c, 0x3c,
+        0x3c, 0x3d, 0xbc, 0xf6, 0x63, 0xdf, 0xec, 0x3c, 0x3c, 0x3f, 0xef, 0x8c, 0xcc, 0x6c, 0x3c, 0x37,
+        0xec, 0x3c, 0x0c, 0x07, 0xe0, 0x30, 0x30, 0x3c, 0x37, 0xef, 0xf1, 0x81, 0x81, 0x81, 0x81, 0x81,
+        0x81, 0x81, 0x81, 0x8c, 0x3c, 0x3c, 0x3c, 0x3c, 0x3c, 0x3c, 0x3c, 0x36, 0x63, 0xcc, 0x3c, 0x3c,
+        0x36, 0x66, 0x66, 0x63, 0xc3, 0xc1, 0x81, 0x8c, 0x3c, 0x3c, 0x3c, 0x3d, 0xbd, 0xbd, 0xbf, 0xfe,
+        0x7c, 0x3c, 0x3c, 0x36, 0x63, 0xc1, 0x81, 0x83, 0xc6, 0x6c, 0x3c, 0x3c, 0x3c, 0x36, 0x63, 0xc1,
+        0x81, 0x81, 0x81, 0x81, 0x81, 0x8f, 0xe0, 0xc1, 0x86, 0x18, 0x61, 0x86, 0x0c, 0x1f, 0xff, 0x8c,
+        0x63, 0x18, 0xc6, 0x31, 0x8c, 0x7f, 0x01, 0x81, 0x80, 0xc0, 0x60, 0x60, 0x30, 0x18, 0x18, 0x0f,
+        0xe3, 0x18, 0xc6, 0x31, 0x8c, 0x63, 0x1f, 0xcc, 0x7b, 0x38, 0x7f, 0xf1, 0x8c, 0xf9, 0x8c, 0x0d,
+        0xff, 0x0f, 0x1d, 0xef, 0x03, 0x03, 0x03, 0x73, 0x9b, 0x0f, 0x0f, 0x0f, 0x9b, 0x70, 0xf9, 0x8f,
+        0x03, 0x03, 0x01, 0x8c, 0xf8, 0x0c, 0x0c, 0x0c, 0xed, 0x9f, 0x0f, 0x0f, 0x0d, 0x9c, 0xec, 0xf1,
+        0x9b, 0x0f, 0xff, 0x01, 0x8c, 0xf8, 0x78, 0xcc, 0xcc, 0xc0, 0xc3, 0xf0, 0xc0, 0xc0, 0xc0, 0xc1,
+        0xf7, 0x1f, 0x1b, 0x19, 0xf3, 0x01, 0xfb, 0x0f, 0x0d, 0xfb, 0x03, 0x03, 0x03, 0x73, 0x9b, 0x0f,
+        0x0f, 0x0f, 0x0f, 0x0c, 0xc3, 0x00, 0x70, 0xc3, 0x0c, 0x30, 0xcf, 0xc1, 0x83, 0x00, 0x3c, 0x18,
+        0x30, 0x60, 0xc1, 0xe3, 0xc7, 0x8d, 0xf6, 0x0c, 0x18, 0x33, 0x6c, 0xf1, 0xe3, 0x66, 0x6c, 0x6e,
+        0x18, 0x61, 0x86, 0x18, 0x61, 0x86, 0x7f, 0x6d, 0xb7, 0xb7, 0xb7, 0xb7, 0xb7, 0xb7, 0xb9, 0xcd,
+        0x87, 0x87, 0x87, 0x87, 0x86, 0x78, 0xcd, 0x87, 0x87, 0x86, 0xcc, 0x79, 0xb9, 0xcd, 0x87, 0x87,
+        0x87, 0xcd, 0xb9, 0x81, 0x81, 0x80, 0x76, 0xcf, 0x87, 0x87, 0x86, 0xce, 0x76, 0x06, 0x06, 0x07,
+        0xbc, 0xe6, 0xc0, 0xc0, 0xc0, 0xc0, 0xc0, 0xfd, 0x87, 0x80, 0xfc, 0x07, 0x86, 0xfc, 0x60, 0x61,
+        0xf8, 0x60, 0x60, 0x60, 0x60, 0x66, 0x3d, 0x87, 0x87, 0x87, 0x87, 0x86, 0xce, 0x77, 0x87, 0x86,
+        0xcc, 0xcc, 0x78, 0x78, 0x31, 0x87, 0x87, 0xb7, 0xb7, 0xb7, 0xfe, 0xcd, 0x86, 0xcc, 0x78, 0x30,
+        0x78, 0xcd, 0x87, 0x87, 0x87, 0x87, 0x87, 0x86, 0xce, 0x76, 0x07, 0x8c, 0xf9, 0xf8, 0x63, 0x18,
+        0xc6, 0x1f, 0x9d, 0x8c, 0x63, 0x31, 0x86, 0x31, 0x8c, 0x3f, 0xff, 0xff, 0xf0, 0xc6, 0x31, 0x86,
+        0x33, 0x18, 0xc6, 0xe3, 0x9e, 0xde, 0x73, 0xd9, 0xe6, 0xf0,
+    ],
+};
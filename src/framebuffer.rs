@@ -0,0 +1,401 @@
+//! Tiled framebuffer with written-pixel flushing
+//!
+//! A full 172x320 RGB565 framebuffer needs 110 KB, more than the heap. This
+//! framebuffer splits the screen into 32x32 tiles and only keeps a fixed
+//! pool of them in memory. Drawing goes into the tiles, which remember
+//! which of their pixels were written, and [`Frame::finish`] sends only
+//! those pixels to the display, in as few rectangles as possible. Pixels
+//! that weren't drawn keep what is on the screen, so partial drawing such
+//! as a chart segment over a grid line is safe.
+//!
+//! A frame is only sent in one go if it fits in the pool. When the pool
+//! runs out, dirty tiles are flushed early and the least recently used
+//! tile is reused, so a frame touching more tiles than the pool holds
+//! (e.g. a full-screen redraw: 60 tiles on 172x320) can be seen in
+//! progress. Size the pool for the largest area that must not tear.
+
+use alloc::{vec, vec::Vec};
+
+use embedded_graphics::{pixelcolor::Rgb565, prelude::*, primitives::Rectangle};
+use embedded_hal::digital::OutputPin;
+use mipidsi::{
+    Display,
+    interface::{Interface, InterfacePixelFormat},
+    models::Model,
+};
+
+/// Width and height of a tile in pixels
+pub const TILE_SIZE: u16 = 32;
+
+/// Pixels in one tile
+const TILE_PIXELS: usize = TILE_SIZE as usize * TILE_SIZE as usize;
+
+/// Display that accepts a rectangle of pixels in one transfer
+pub trait FlushTarget {
+    type Error;
+
+    /// Write `pixels` row by row into `area`
+    fn flush_area<I>(&mut self, area: &Rectangle, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Rgb565>;
+}
+
+impl<DI, M, RST> FlushTarget for Display<DI, M, RST>
+where
+    DI: Interface,
+    M: Model<ColorFormat = Rgb565>,
+    Rgb565: InterfacePixelFormat<DI::Word>,
+    RST: OutputPin,
+{
+    type Error = DI::Error;
+
+    fn flush_area<I>(&mut self, area: &Rectangle, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Rgb565>,
+    {
+        let Some(end) = area.bottom_right() else {
+            return Ok(());
+        };
+        self.set_pixels(
+            area.top_left.x as u16,
+            area.top_left.y as u16,
+            end.x as u16,
+            end.y as u16,
+            pixels,
+        )
+    }
+}
+
+/// Flush counters, for comparing against drawing straight to the display
+#[derive(Clone, Copy, Default, Debug)]
+pub struct FlushStats {
+    /// Tiles sent to the display
+    pub tiles_flushed: u32,
+    /// Pixels sent to the display
+    pub pixels_flushed: u32,
+    /// Tiles that had to be flushed before the end of a frame because the pool was full
+    pub early_flushes: u32,
+    /// Tiles dropped from the pool to make room for another tile
+    pub evictions: u32,
+}
+
+/// Pixels written since the last flush, one bit per pixel, a row per word
+type WrittenMask = [u32; TILE_SIZE as usize];
+
+// A tile row has to fit in one mask word
+const _: () = assert!(TILE_SIZE == u32::BITS as u16);
+
+/// Bits `x0..=x1` of a mask row
+fn row_bits(x0: u16, x1: u16) -> u32 {
+    (u32::MAX >> (u32::BITS as u16 - 1 - (x1 - x0))) << x0
+}
+
+/// One tile buffer in the pool
+#[derive(Clone, Copy, Default)]
+struct Slot {
+    /// Screen tile held by this slot
+    tile: Option<usize>,
+    /// Pixels changed since the last flush
+    written: WrittenMask,
+    /// Use counter value of the last draw, for LRU eviction
+    last_used: u32,
+}
+
+impl Slot {
+    fn is_dirty(&self) -> bool {
+        self.written.iter().any(|&row| row != 0)
+    }
+}
+
+/// Framebuffer keeping a pool of screen tiles in memory
+pub struct TiledFramebuffer {
+    width: u16,
+    height: u16,
+    columns: usize,
+    background: Rgb565,
+    pixels: Vec<Rgb565>,
+    slots: Vec<Slot>,
+    /// Slot holding each screen tile, if any
+    map: Vec<Option<usize>>,
+    use_counter: u32,
+    stats: FlushStats,
+}
+
+impl TiledFramebuffer {
+    /// Create a framebuffer
+    ///
+    /// # Arguments
+    /// * `width` - Display width in pixels
+    /// * `height` - Display height in pixels
+    /// * `max_tiles` - Tiles kept in memory, 2 KB each
+    /// * `background` - Color the display was cleared to
+    pub fn new(width: u16, height: u16, max_tiles: usize, background: Rgb565) -> Self {
+        let columns = width.div_ceil(TILE_SIZE) as usize;
+        let rows = height.div_ceil(TILE_SIZE) as usize;
+        let max_tiles = max_tiles.clamp(1, columns * rows);
+
+        Self {
+            width,
+            height,
+            columns,
+            background,
+            pixels: vec![background; max_tiles * TILE_PIXELS],
+            slots: vec![Slot::default(); max_tiles],
+            map: vec![None; columns * rows],
+            use_counter: 0,
+            stats: FlushStats::default(),
+        }
+    }
+
+    /// Get the background color
+    pub fn background(&self) -> Rgb565 {
+        self.background
+    }
+
+    /// Get the flush counters
+    pub fn stats(&self) -> FlushStats {
+        self.stats
+    }
+
+    /// Number of tiles with changes that haven't been flushed
+    pub fn dirty_tiles(&self) -> usize {
+        self.slots.iter().filter(|s| s.is_dirty()).count()
+    }
+
+    /// Start drawing a frame on `target`
+    ///
+    /// Draw on the returned [`Frame`] and call [`Frame::finish`] to flush it.
+    pub fn frame<'a, T: FlushTarget>(&'a mut self, target: &'a mut T) -> Frame<'a, T> {
+        Frame { fb: self, target }
+    }
+
+    /// Forget all tiles, e.g. after something was drawn around the framebuffer
+    ///
+    /// Unflushed changes are lost.
+    pub fn invalidate(&mut self) {
+        self.slots.iter_mut().for_each(|s| *s = Slot::default());
+        self.map.iter_mut().for_each(|m| *m = None);
+    }
+
+    /// Send every dirty tile to `target`
+    pub fn flush<T: FlushTarget>(&mut self, target: &mut T) -> Result<(), T::Error> {
+        for slot in 0..self.slots.len() {
+            self.flush_slot(target, slot)?;
+        }
+        Ok(())
+    }
+
+    fn flush_slot<T: FlushTarget>(&mut self, target: &mut T, slot: usize) -> Result<(), T::Error> {
+        let Some(tile) = self.slots[slot].tile else {
+            return Ok(());
+        };
+        let written = self.slots[slot].written;
+        if written.iter().all(|&row| row == 0) {
+            return Ok(());
+        }
+
+        // Rows with the same mask are sent together, one rectangle per run
+        // of written pixels, so a fully redrawn area is a single transfer
+        let origin = self.tile_origin(tile);
+        let buffer = &self.pixels[slot * TILE_PIXELS..(slot + 1) * TILE_PIXELS];
+        let mut y0 = 0;
+        while y0 < written.len() {
+            let mask = written[y0];
+            let rows = written[y0..].iter().take_while(|&&row| row == mask).count();
+            let mut bits = mask;
+            while bits != 0 {
+                let x0 = bits.trailing_zeros() as u16;
+                let x1 = x0 + (bits >> x0).trailing_ones() as u16 - 1;
+                bits &= !row_bits(x0, x1);
+
+                let width = (x1 - x0 + 1) as u32;
+                let area = Rectangle::new(
+                    origin + Point::new(x0 as i32, y0 as i32),
+                    Size::new(width, rows as u32),
+                );
+                let pixels = (y0..y0 + rows).flat_map(move |y| {
+                    let row = y * TILE_SIZE as usize;
+                    buffer[row + x0 as usize..=row + x1 as usize]
+                        .iter()
+                        .copied()
+                });
+                target.flush_area(&area, pixels)?;
+                self.stats.pixels_flushed += width * rows as u32;
+            }
+            y0 += rows;
+        }
+
+        self.slots[slot].written = WrittenMask::default();
+        self.stats.tiles_flushed += 1;
+        Ok(())
+    }
+
+    /// Get the slot for a screen tile, loading it into the pool if needed
+    fn slot_for<T: FlushTarget>(&mut self, target: &mut T, tile: usize) -> Result<usize, T::Error> {
+        self.use_counter = self.use_counter.wrapping_add(1);
+
+        if let Some(slot) = self.map[tile] {
+            self.slots[slot].last_used = self.use_counter;
+            return Ok(slot);
+        }
+
+        let slot = match self.slots.iter().position(|s| s.tile.is_none()) {
+            Some(free) => free,
+            None => {
+                // Prefer a clean tile, flush everything if there is none
+                let slot = match self.least_recently_used(true) {
+                    Some(clean) => clean,
+                    None => {
+                        self.stats.early_flushes += self.dirty_tiles() as u32;
+                        self.flush(target)?;
+                        self.least_recently_used(false).unwrap_or(0)
+                    }
+                };
+                if let Some(old) = self.slots[slot].tile {
+                    self.map[old] = None;
+                }
+                self.stats.evictions += 1;
+                slot
+            }
+        };
+
+        // Unwritten pixels are never sent, so the old content can stay
+        self.slots[slot] = Slot {
+            tile: Some(tile),
+            written: WrittenMask::default(),
+            last_used: self.use_counter,
+        };
+        self.map[tile] = Some(slot);
+        Ok(slot)
+    }
+
+    fn least_recently_used(&self, clean_only: bool) -> Option<usize> {
+        self.slots
+            .iter()
+            .enumerate()
+            .filter(|(_, s)| !clean_only || !s.is_dirty())
+            .max_by_key(|(_, s)| self.use_counter.wrapping_sub(s.last_used))
+            .map(|(i, _)| i)
+    }
+
+    fn tile_origin(&self, tile: usize) -> Point {
+        Point::new(
+            ((tile % self.columns) * TILE_SIZE as usize) as i32,
+            ((tile / self.columns) * TILE_SIZE as usize) as i32,
+        )
+    }
+}
+
+/// Frame being drawn into a [`TiledFramebuffer`]
+pub struct Frame<'a, T: FlushTarget> {
+    fb: &'a mut TiledFramebuffer,
+    target: &'a mut T,
+}
+
+impl<T: FlushTarget> Frame<'_, T> {
+    /// Flush all dirty tiles to the display
+    pub fn finish(self) -> Result<(), T::Error> {
+        self.fb.flush(self.target)
+    }
+}
+
+impl<T: FlushTarget> OriginDimensions for Frame<'_, T> {
+    fn size(&self) -> Size {
+        Size::new(self.fb.width as u32, self.fb.height as u32)
+    }
+}
+
+impl<T: FlushTarget> DrawTarget for Frame<'_, T> {
+    type Color = Rgb565;
+    type Error = T::Error;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        // Pixels usually arrive in runs on the same tile
+        let mut cached: Option<(usize, usize)> = None;
+
+        for Pixel(point, color) in pixels {
+            if point.x < 0
+                || point.y < 0
+                || point.x >= self.fb.width as i32
+                || point.y >= self.fb.height as i32
+            {
+                continue;
+            }
+            let (x, y) = (point.x as u16, point.y as u16);
+            let tile = (y / TILE_SIZE) as usize * self.fb.columns + (x / TILE_SIZE) as usize;
+
+            let slot = match cached {
+                // Still in the pool unless a flush evicted it
+                Some((t, s)) if t == tile && self.fb.slots[s].tile == Some(tile) => s,
+                _ => {
+                    let s = self.fb.slot_for(self.target, tile)?;
+                    cached = Some((tile, s));
+                    s
+                }
+            };
+
+            let (tx, ty) = (x % TILE_SIZE, y % TILE_SIZE);
+            self.fb.pixels[slot * TILE_PIXELS + ty as usize * TILE_SIZE as usize + tx as usize] =
+                color;
+            self.fb.slots[slot].written[ty as usize] |= 1 << tx;
+        }
+        Ok(())
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        let screen = self.bounding_box();
+        let area = area.intersection(&screen);
+        if area.size == screen.size {
+            return self.clear(color);
+        }
+        let Some(end) = area.bottom_right() else {
+            return Ok(());
+        };
+
+        // Fill row runs tile by tile instead of pixel by pixel
+        let size = TILE_SIZE as i32;
+        for row in area.top_left.y / size..=end.y / size {
+            for column in area.top_left.x / size..=end.x / size {
+                let origin = Point::new(column * size, row * size);
+                let part =
+                    area.intersection(&Rectangle::new(origin, Size::new_equal(TILE_SIZE as u32)));
+                let Some(part_end) = part.bottom_right() else {
+                    continue;
+                };
+
+                let tile = row as usize * self.fb.columns + column as usize;
+                let slot = self.fb.slot_for(self.target, tile)?;
+                let (x0, y0) = (
+                    (part.top_left.x - origin.x) as u16,
+                    (part.top_left.y - origin.y) as u16,
+                );
+                let (x1, y1) = (
+                    (part_end.x - origin.x) as u16,
+                    (part_end.y - origin.y) as u16,
+                );
+
+                for y in y0..=y1 {
+                    let start = slot * TILE_PIXELS + y as usize * TILE_SIZE as usize;
+                    self.fb.pixels[start + x0 as usize..=start + x1 as usize].fill(color);
+                    self.fb.slots[slot].written[y as usize] |= row_bits(x0, x1);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        // Send the whole screen in one transfer and make it the new background
+        self.fb.invalidate();
+        self.fb.background = color;
+        let area = self.bounding_box();
+        let count = area.size.width * area.size.height;
+        self.target
+            .flush_area(&area, core::iter::repeat_n(color, count as usize))?;
+        self.fb.stats.pixels_flushed += count;
+        Ok(())
+    }
+}
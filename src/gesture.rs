@@ -0,0 +1,208 @@
+//! Swipe and edge-swipe detection
+//!
+//! [`GestureDetector`] consumes [`TouchEvent`]s of a single finger and
+//! reports a [`Gesture`] when it is lifted. Swipes that start within
+//! `edge_margin` pixels of a screen edge and move away from it are reported
+//! as [`Gesture::EdgeSwipe`], so they can be bound to system actions such
+//! as "back" or a quick-settings panel.
+
+use crate::axs5106l::TouchEvent;
+
+/// Direction of a swipe
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Direction {
+    Left,
+    Right,
+    Up,
+    Down,
+}
+
+/// Screen edge an edge swipe started from
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Edge {
+    Left,
+    Right,
+    Top,
+    Bottom,
+}
+
+impl Edge {
+    /// Direction that moves away from the edge into the screen
+    pub fn inward(self) -> Direction {
+        match self {
+            Edge::Left => Direction::Right,
+            Edge::Right => Direction::Left,
+            Edge::Top => Direction::Down,
+            Edge::Bottom => Direction::Up,
+        }
+    }
+}
+
+/// Detected gesture
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Gesture {
+    /// Swipe starting anywhere on the screen
+    Swipe { direction: Direction },
+    /// Swipe starting at a screen edge, moving into the screen
+    EdgeSwipe { edge: Edge },
+}
+
+/// Thresholds for gesture detection
+#[derive(Clone, Copy, Debug)]
+pub struct GestureConfig {
+    /// Display width in pixels
+    pub width: u16,
+    /// Display height in pixels
+    pub height: u16,
+    /// Distance from an edge that counts as starting on it (pixels)
+    pub edge_margin: u16,
+    /// Shortest movement that counts as a swipe (pixels)
+    pub min_distance: u16,
+    /// Longest time from touch to release for a swipe (us)
+    pub max_duration_us: u64,
+}
+
+impl GestureConfig {
+    /// Create a configuration with default thresholds for a display
+    pub fn new(width: u16, height: u16) -> Self {
+        Self {
+            width,
+            height,
+            edge_margin: 12,
+            min_distance: 40,
+            max_duration_us: 600_000,
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+struct Start {
+    id: u8,
+    x: u16,
+    y: u16,
+    timestamp_us: u64,
+}
+
+/// Single-finger swipe detector
+pub struct GestureDetector {
+    config: GestureConfig,
+    start: Option<Start>,
+    cancelled: bool,
+}
+
+impl GestureDetector {
+    /// Create a new detector
+    pub fn new(config: GestureConfig) -> Self {
+        Self {
+            config,
+            start: None,
+            cancelled: false,
+        }
+    }
+
+    /// Get the configuration
+    pub fn config(&self) -> &GestureConfig {
+        &self.config
+    }
+
+    /// Feed a touch event, returns a gesture when the finger is lifted
+    ///
+    /// A second finger touching the panel cancels the gesture.
+    pub fn update(&mut self, event: &TouchEvent) -> Option<Gesture> {
+        match *event {
+            TouchEvent::Down {
+                id,
+                x,
+                y,
+                timestamp_us,
+            } => {
+                if self.start.is_some() {
+                    self.cancelled = true;
+                } else {
+                    self.cancelled = false;
+                    self.start = Some(Start {
+                        id,
+                        x,
+                        y,
+                        timestamp_us,
+                    });
+                }
+                None
+            }
+            TouchEvent::Up {
+                id,
+                x,
+                y,
+                timestamp_us,
+            } => {
+                let start = self.start.filter(|s| s.id == id)?;
+                self.start = None;
+                if self.cancelled {
+                    return None;
+                }
+                self.classify(start, x, y, timestamp_us)
+            }
+            TouchEvent::Move { .. }
+            | TouchEvent::KeyPress { .. }
+            | TouchEvent::KeyRelease { .. } => None,
+        }
+    }
+
+    /// Forget the current finger
+    pub fn reset(&mut self) {
+        self.start = None;
+        self.cancelled = false;
+    }
+
+    fn classify(&self, start: Start, x: u16, y: u16, timestamp_us: u64) -> Option<Gesture> {
+        if timestamp_us.saturating_sub(start.timestamp_us) > self.config.max_duration_us {
+            return None;
+        }
+
+        let dx = x as i32 - start.x as i32;
+        let dy = y as i32 - start.y as i32;
+        let min_distance = self.config.min_distance as i32;
+
+        // The dominant axis decides the direction, it has to be clearly dominant
+        let direction = if dx.abs() >= dy.abs() * 2 && dx.abs() >= min_distance {
+            if dx > 0 {
+                Direction::Right
+            } else {
+                Direction::Left
+            }
+        } else if dy.abs() >= dx.abs() * 2 && dy.abs() >= min_distance {
+            if dy > 0 {
+                Direction::Down
+            } else {
+                Direction::Up
+            }
+        } else {
+            return None;
+        };
+
+        match self.start_edge(start, direction) {
+            Some(edge) => Some(Gesture::EdgeSwipe { edge }),
+            None => Some(Gesture::Swipe { direction }),
+        }
+    }
+
+    /// Edge the swipe started on and moved away from, if any
+    ///
+    /// A start in a corner is near two edges, the one the swipe moves away
+    /// from wins.
+    fn start_edge(&self, start: Start, direction: Direction) -> Option<Edge> {
+        let margin = self.config.edge_margin;
+        let right = self.config.width.saturating_sub(1).saturating_sub(margin);
+        let bottom = self.config.height.saturating_sub(1).saturating_sub(margin);
+
+        [
+            (Edge::Left, start.x < margin),
+            (Edge::Right, start.x > right),
+            (Edge::Top, start.y < margin),
+            (Edge::Bottom, start.y > bottom),
+        ]
+        .into_iter()
+        .find(|&(edge, near)| near && edge.inward() == direction)
+        .map(|(edge, _)| edge)
+    }
+}
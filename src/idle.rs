@@ -0,0 +1,151 @@
+//! Inactivity handling for the display backlight
+//!
+//! Tracks the time since the last touch and steps the display through
+//! `Active -> Dimmed -> Off`. The manager does not own any hardware; it
+//! returns an [`IdleAction`] that the caller applies to the backlight PWM
+//! and the display controller (SLPIN / SLPOUT).
+
+/// Idle timing and backlight levels
+#[derive(Clone, Copy)]
+pub struct IdleConfig {
+    /// Time without touches before the backlight is dimmed (ms)
+    pub dim_after_ms: u64,
+    /// Time without touches before the display is put to sleep (ms)
+    pub off_after_ms: u64,
+    /// Backlight duty in percent while active
+    pub active_duty: u8,
+    /// Backlight duty in percent while dimmed
+    pub dim_duty: u8,
+}
+
+impl Default for IdleConfig {
+    fn default() -> Self {
+        Self {
+            dim_after_ms: 15_000,
+            off_after_ms: 45_000,
+            active_duty: 80,
+            dim_duty: 10,
+        }
+    }
+}
+
+/// Current power state of the display
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum IdleState {
+    Active,
+    Dimmed,
+    Off,
+}
+
+/// Action the caller has to apply to the hardware after a state change
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum IdleAction {
+    /// Set the backlight to the given duty (percent)
+    SetBacklight(u8),
+    /// Turn the backlight off and send the display to sleep (SLPIN)
+    ScreenOff,
+    /// Wake the display (SLPOUT) and restore the backlight to the given duty
+    WakeUp(u8),
+}
+
+/// Backlight dimming and screen-off state machine
+pub struct IdleManager {
+    config: IdleConfig,
+    state: IdleState,
+    last_activity_ms: u64,
+    swallow_touch: bool,
+}
+
+impl IdleManager {
+    /// Create a new idle manager
+    ///
+    /// # Arguments
+    /// * `config` - Idle timing and backlight levels
+    /// * `now_ms` - Current monotonic time in milliseconds
+    pub fn new(config: IdleConfig, now_ms: u64) -> Self {
+        Self {
+            config,
+            state: IdleState::Active,
+            last_activity_ms: now_ms,
+            swallow_touch: false,
+        }
+    }
+
+    /// Get the current power state
+    pub fn state(&self) -> IdleState {
+        self.state
+    }
+
+    /// Get the configuration
+    pub fn config(&self) -> &IdleConfig {
+        &self.config
+    }
+
+    /// Set the backlight duty used while active
+    ///
+    /// Only changes the level restored by later actions, the caller applies
+    /// it to the backlight itself if the state is [`IdleState::Active`].
+    pub fn set_active_duty(&mut self, duty: u8) {
+        self.config.active_duty = duty;
+    }
+
+    /// Advance the idle timers
+    ///
+    /// Call this periodically from the main loop. Returns the action to apply
+    /// if the display should change state.
+    pub fn update(&mut self, now_ms: u64) -> Option<IdleAction> {
+        let idle_ms = now_ms.saturating_sub(self.last_activity_ms);
+
+        match self.state {
+            IdleState::Active if idle_ms >= self.config.off_after_ms => {
+                self.state = IdleState::Off;
+                Some(IdleAction::ScreenOff)
+            }
+            IdleState::Active if idle_ms >= self.config.dim_after_ms => {
+                self.state = IdleState::Dimmed;
+                Some(IdleAction::SetBacklight(self.config.dim_duty))
+            }
+            IdleState::Dimmed if idle_ms >= self.config.off_after_ms => {
+                self.state = IdleState::Off;
+                Some(IdleAction::ScreenOff)
+            }
+            _ => None,
+        }
+    }
+
+    /// Report touch activity
+    ///
+    /// Call this for every touch interrupt, before the touch is delivered to
+    /// the application. `touching` is true while at least one finger is down.
+    ///
+    /// Returns the action to apply (if the display has to be woken) and
+    /// whether the touch should be delivered. A touch that wakes the display
+    /// from `Off` is swallowed until the finger is lifted, so it can't press
+    /// whatever is under it once the screen comes back.
+    pub fn on_touch(&mut self, now_ms: u64, touching: bool) -> (Option<IdleAction>, bool) {
+        self.last_activity_ms = now_ms;
+
+        let action = match self.state {
+            IdleState::Active => None,
+            IdleState::Dimmed => {
+                self.state = IdleState::Active;
+                Some(IdleAction::SetBacklight(self.config.active_duty))
+            }
+            IdleState::Off => {
+                self.state = IdleState::Active;
+                self.swallow_touch = touching;
+                return (Some(IdleAction::WakeUp(self.config.active_duty)), false);
+            }
+        };
+
+        if self.swallow_touch {
+            // Keep swallowing until the waking finger has been released
+            if !touching {
+                self.swallow_touch = false;
+            }
+            return (action, false);
+        }
+
+        (action, true)
+    }
+}
@@ -1,3 +1,14 @@
 #![no_std]
 
+extern crate alloc;
+
 pub mod axs5106l;
+pub mod clock;
+pub mod diagnostics;
+pub mod display_interface;
+pub mod font;
+pub mod framebuffer;
+pub mod gesture;
+pub mod idle;
+pub mod qoi;
+pub mod ui;
@@ -0,0 +1,268 @@
+//! QOI images drawn straight from flash
+//!
+//! [QOI](https://qoiformat.org) compresses icons and splash images about as
+//! well as PNG but decodes in a single pass with 256 bytes of state, so
+//! images stay compressed in flash and are decoded while drawing:
+//!
+//! ```ignore
+//! static BATTERY: &[u8] = include_bytes!("../../assets/battery.qoi");
+//!
+//! let icon = Qoi::new(BATTERY)?.with_background(Rgb565::BLACK);
+//! Image::new(&icon, Point::new(10, 10)).draw(&mut display)?;
+//! ```
+//!
+//! Pixels with an alpha of 0 are not drawn, so whatever is below shows
+//! through. Partly transparent pixels are blended with the background
+//! color if one is set, otherwise drawn when at least half opaque. Images
+//! without alpha can instead make one color transparent with
+//! [`Qoi::with_key`].
+//!
+//! Convert PNG files with `tools/png2qoi.py`.
+
+use embedded_graphics::{
+    image::ImageDrawable,
+    pixelcolor::{Rgb565, Rgb888},
+    prelude::*,
+    primitives::Rectangle,
+};
+
+/// Start of every QOI file
+const MAGIC: &[u8; 4] = b"qoif";
+/// Length of the header
+const HEADER_LEN: usize = 14;
+/// Bytes after the last chunk
+const END: [u8; 8] = [0, 0, 0, 0, 0, 0, 0, 1];
+/// Most pixels a single chunk byte can stand for (a full `OP_RUN`)
+const MAX_RUN: usize = 62;
+
+const OP_RGB: u8 = 0xfe;
+const OP_RGBA: u8 = 0xff;
+const OP_INDEX: u8 = 0x00;
+const OP_DIFF: u8 = 0x40;
+const OP_LUMA: u8 = 0x80;
+const OP_MASK: u8 = 0xc0;
+
+/// Data is not a QOI image this decoder can draw
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum QoiError {
+    /// Missing "qoif" magic or the file is shorter than the header
+    NotQoi,
+    /// Channels is neither 3 nor 4, or the size is zero or larger than the
+    /// chunk data can describe
+    InvalidHeader,
+    /// The end marker is missing
+    Truncated,
+}
+
+/// QOI image that decodes while drawing
+#[derive(Clone, Copy, Debug)]
+pub struct Qoi<'a> {
+    /// Chunks between the header and the end marker
+    data: &'a [u8],
+    size: Size,
+    alpha: bool,
+    background: Option<Rgb565>,
+    key: Option<Rgb565>,
+}
+
+impl<'a> Qoi<'a> {
+    /// Check the header of a QOI file
+    pub fn new(data: &'a [u8]) -> Result<Self, QoiError> {
+        if data.len() < HEADER_LEN || !data.starts_with(MAGIC) {
+            return Err(QoiError::NotQoi);
+        }
+        let be = |i: usize| u32::from_be_bytes([data[i], data[i + 1], data[i + 2], data[i + 3]]);
+        let size = Size::new(be(4), be(8));
+        let channels = data[12];
+        if !matches!(channels, 3 | 4) || size.width == 0 || size.height == 0 {
+            return Err(QoiError::InvalidHeader);
+        }
+        let data = data[HEADER_LEN..]
+            .strip_suffix(&END)
+            .ok_or(QoiError::Truncated)?;
+        // Every chunk byte covers at most one run of pixels
+        let pixels = (size.width as usize)
+            .checked_mul(size.height as usize)
+            .ok_or(QoiError::InvalidHeader)?;
+        if pixels > data.len().saturating_mul(MAX_RUN) {
+            return Err(QoiError::InvalidHeader);
+        }
+        Ok(Self {
+            data,
+            size,
+            alpha: channels == 4,
+            background: None,
+            key: None,
+        })
+    }
+
+    /// Blend partly transparent pixels with `color`, usually the color
+    /// the image is drawn on
+    pub fn with_background(mut self, color: Rgb565) -> Self {
+        self.background = Some(color);
+        self
+    }
+
+    /// Don't draw pixels of `color`, for images without alpha
+    pub fn with_key(mut self, color: Rgb565) -> Self {
+        self.key = Some(color);
+        self
+    }
+
+    /// Whether every pixel is drawn
+    fn is_opaque(&self) -> bool {
+        !self.alpha && self.key.is_none()
+    }
+
+    /// Decoded pixels, row by row
+    fn pixels(&self) -> Pixels<'a> {
+        Pixels {
+            data: self.data,
+            index: [[0; 4]; 64],
+            pixel: [0, 0, 0, 255],
+            run: 0,
+            // Can't overflow, checked in new()
+            left: self.size.width as usize * self.size.height as usize,
+        }
+    }
+
+    /// Screen color of a decoded pixel, None if it's transparent
+    fn color(&self, [r, g, b, a]: [u8; 4]) -> Option<Rgb565> {
+        let color = Rgb565::from(Rgb888::new(r, g, b));
+        if Some(color) == self.key || a == 0 {
+            return None;
+        }
+        match (a, self.background) {
+            (255, _) => Some(color),
+            (a, Some(background)) => Some(blend(color, background, a)),
+            (a, None) => (a >= 128).then_some(color),
+        }
+    }
+}
+
+impl OriginDimensions for Qoi<'_> {
+    fn size(&self) -> Size {
+        self.size
+    }
+}
+
+impl ImageDrawable for Qoi<'_> {
+    type Color = Rgb565;
+
+    fn draw<D>(&self, target: &mut D) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>,
+    {
+        self.draw_sub_image(target, &self.bounding_box())
+    }
+
+    fn draw_sub_image<D>(&self, target: &mut D, area: &Rectangle) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>,
+    {
+        let area = area.intersection(&self.bounding_box());
+        if area.is_zero_sized() {
+            return Ok(());
+        }
+        let width = self.size.width as usize;
+        let inside = move |(i, pixel): (usize, [u8; 4])| {
+            let point = Point::new((i % width) as i32, (i / width) as i32);
+            area.contains(point)
+                .then_some((point - area.top_left, pixel))
+        };
+
+        if self.is_opaque() {
+            // Every pixel is drawn, in order, so the target can take them
+            // as one block
+            let colors = self
+                .pixels()
+                .enumerate()
+                .filter_map(inside)
+                .map(|(_, [r, g, b, _])| Rgb565::from(Rgb888::new(r, g, b)));
+            target.fill_contiguous(&Rectangle::new(Point::zero(), area.size), colors)
+        } else {
+            let pixels = self
+                .pixels()
+                .enumerate()
+                .filter_map(inside)
+                .filter_map(|(point, pixel)| Some(Pixel(point, self.color(pixel)?)));
+            target.draw_iter(pixels)
+        }
+    }
+}
+
+/// Decoder state, yields RGBA pixels
+struct Pixels<'a> {
+    data: &'a [u8],
+    /// Recently seen pixels by hash
+    index: [[u8; 4]; 64],
+    pixel: [u8; 4],
+    /// Repeats of `pixel` still to yield
+    run: u8,
+    /// Pixels still to yield
+    left: usize,
+}
+
+impl Pixels<'_> {
+    fn byte(&mut self) -> u8 {
+        // Truncated data decodes as zeros instead of panicking
+        let (&byte, rest) = self.data.split_first().unwrap_or((&0, &[]));
+        self.data = rest;
+        byte
+    }
+}
+
+impl Iterator for Pixels<'_> {
+    type Item = [u8; 4];
+
+    fn next(&mut self) -> Option<[u8; 4]> {
+        if self.left == 0 {
+            return None;
+        }
+        self.left -= 1;
+        if self.run > 0 {
+            self.run -= 1;
+            return Some(self.pixel);
+        }
+
+        let op = self.byte();
+        match op {
+            OP_RGB => {
+                let rgb = [self.byte(), self.byte(), self.byte()];
+                self.pixel[..3].copy_from_slice(&rgb);
+            }
+            OP_RGBA => self.pixel = [self.byte(), self.byte(), self.byte(), self.byte()],
+            _ if op & OP_MASK == OP_INDEX => self.pixel = self.index[op as usize],
+            _ if op & OP_MASK == OP_DIFF => {
+                let [r, g, b, _] = &mut self.pixel;
+                *r = r.wrapping_add((op >> 4 & 3).wrapping_sub(2));
+                *g = g.wrapping_add((op >> 2 & 3).wrapping_sub(2));
+                *b = b.wrapping_add((op & 3).wrapping_sub(2));
+            }
+            _ if op & OP_MASK == OP_LUMA => {
+                let next = self.byte();
+                let [r, g, b, _] = &mut self.pixel;
+                let dg = (op & 0x3f).wrapping_sub(32);
+                *r = r.wrapping_add(dg.wrapping_add(next >> 4).wrapping_sub(8));
+                *g = g.wrapping_add(dg);
+                *b = b.wrapping_add(dg.wrapping_add(next & 0x0f).wrapping_sub(8));
+            }
+            // OP_RUN, this pixel and `run` more
+            _ => self.run = op & 0x3f,
+        }
+        let [r, g, b, a] = self.pixel.map(|c| c as usize);
+        self.index[(r * 3 + g * 5 + b * 7 + a * 11) % 64] = self.pixel;
+        Some(self.pixel)
+    }
+}
+
+/// Mix `color` over `background`, `alpha` of 255 is all `color`
+fn blend(color: Rgb565, background: Rgb565, alpha: u8) -> Rgb565 {
+    let mix =
+        |c: u8, b: u8| ((c as u16 * alpha as u16 + b as u16 * (255 - alpha as u16)) / 255) as u8;
+    Rgb565::new(
+        mix(color.r(), background.r()),
+        mix(color.g(), background.g()),
+        mix(color.b(), background.b()),
+    )
+}
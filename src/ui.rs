@@ -0,0 +1,337 @@
+//! Retained widget toolkit
+//!
+//! Widgets keep their own state, redraw themselves only when something
+//! changed and react to [`TouchEvent`]s from the touch driver. A [`Ui`]
+//! holds a set of widgets, routes touches to the widget under the finger
+//! and draws the dirty ones:
+//!
+//! ```ignore
+//! let mut ui = Ui::new();
+//! let ok = ui.add(Button::new(Rectangle::new(Point::new(10, 10), Size::new(80, 32)), "OK")
+//!     .on_click(|| println!("OK")));
+//!
+//! while let Some(event) = TOUCH_EVENTS.pop() {
+//!     ui.handle_event(&event);
+//! }
+//! ui.draw(&mut display)?;
+//! ```
+//!
+//! Widgets draw on a [`Canvas`] rather than a generic `DrawTarget`, so they
+//! can be stored as trait objects. Every widget paints its whole bounds,
+//! which keeps it compatible with the tiled framebuffer. Colors, fonts and
+//! sizes come from the current [`Theme`].
+
+use alloc::{boxed::Box, vec::Vec};
+use core::any::Any;
+
+use embedded_graphics::{pixelcolor::Rgb565, prelude::*, primitives::Rectangle, text::Alignment};
+
+use crate::{
+    axs5106l::{Motion, TouchEvent},
+    font::{Font, TextBox},
+};
+
+mod button;
+mod canvas;
+mod chart;
+mod checkbox;
+mod icon;
+mod keyboard;
+mod label;
+mod list;
+mod navigator;
+mod progress;
+mod slider;
+mod text_field;
+mod theme;
+mod toggle;
+
+pub use button::Button;
+pub use canvas::{Canvas, DrawError, with_canvas};
+pub use chart::Chart;
+pub use checkbox::Checkbox;
+pub use icon::Icon;
+pub use keyboard::{Key, Keyboard};
+pub use label::Label;
+pub use list::ScrollList;
+pub use navigator::{Navigation, Navigator, Screen};
+pub use progress::ProgressBar;
+pub use slider::Slider;
+pub use text_field::TextField;
+pub use theme::{Theme, set_theme, theme};
+pub use toggle::Toggle;
+
+/// Fingers that can hold a widget at the same time
+const MAX_CAPTURES: usize = 5;
+
+/// Something a widget reports after a touch
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum WidgetEvent {
+    /// A button was pressed and released inside its bounds
+    Clicked,
+    /// A toggle or checkbox changed to the given state
+    Toggled(bool),
+    /// A slider moved to the given value
+    Changed(i32),
+    /// A list row was tapped
+    Selected(usize),
+    /// A key was typed on the keyboard
+    Key(Key),
+}
+
+/// State shared by all widgets
+pub struct WidgetBase {
+    bounds: Rectangle,
+    enabled: bool,
+    pressed: bool,
+    dirty: bool,
+}
+
+impl WidgetBase {
+    /// Create the state of a widget covering `bounds`
+    pub fn new(bounds: Rectangle) -> Self {
+        Self {
+            bounds,
+            enabled: true,
+            pressed: false,
+            dirty: true,
+        }
+    }
+
+    /// Area covered by the widget
+    pub fn bounds(&self) -> Rectangle {
+        self.bounds
+    }
+
+    /// Whether the widget reacts to touches
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Whether a finger is currently pressing the widget
+    pub fn is_pressed(&self) -> bool {
+        self.pressed
+    }
+
+    /// Whether the widget has to be redrawn
+    pub fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    /// Enable or disable the widget
+    pub fn set_enabled(&mut self, enabled: bool) {
+        if self.enabled != enabled {
+            self.enabled = enabled;
+            self.pressed = false;
+            self.dirty = true;
+        }
+    }
+
+    /// Set the pressed state, redrawing if it changed
+    pub fn set_pressed(&mut self, pressed: bool) {
+        if self.pressed != pressed {
+            self.pressed = pressed;
+            self.dirty = true;
+        }
+    }
+
+    /// Mark the widget for redrawing
+    pub fn invalidate(&mut self) {
+        self.dirty = true;
+    }
+
+    /// Mark the widget as drawn
+    pub fn mark_drawn(&mut self) {
+        self.dirty = false;
+    }
+}
+
+/// A retained widget
+pub trait Widget: Any {
+    /// Shared widget state
+    fn base(&self) -> &WidgetBase;
+
+    /// Shared widget state, mutable
+    fn base_mut(&mut self) -> &mut WidgetBase;
+
+    /// Paint the whole widget
+    fn draw(&mut self, canvas: &mut Canvas<'_>) -> Result<(), DrawError>;
+
+    /// Handle a touch event
+    ///
+    /// The widget gets the `Down` event of a finger inside its bounds and
+    /// every later event of that finger, wherever it goes.
+    fn touch(&mut self, _event: &TouchEvent) -> Option<WidgetEvent> {
+        None
+    }
+
+    /// Release velocity of the finger whose `Up` the widget just handled
+    fn fling(&mut self, _motion: &Motion) {}
+
+    /// Advance animations, called once per frame
+    fn update(&mut self, _now_ms: u64) {}
+
+    /// Repaint everything on the next draw
+    fn invalidate(&mut self) {
+        self.base_mut().invalidate();
+    }
+}
+
+/// Handle of a widget added to a [`Ui`]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct WidgetId(usize);
+
+/// Set of widgets on one screen
+#[derive(Default)]
+pub struct Ui {
+    widgets: Vec<Box<dyn Widget>>,
+    /// Finger ID and the widget it pressed
+    captures: [Option<(u8, usize)>; MAX_CAPTURES],
+    /// Clear the whole target on a full redraw
+    opaque: bool,
+    cleared: bool,
+    /// Finger ID and widget of the last release, for [`fling`](Self::fling)
+    released: Option<(u8, usize)>,
+}
+
+impl Ui {
+    /// Create an empty UI
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Clear the whole target to the theme background before a full redraw
+    ///
+    /// Otherwise the UI only paints its widgets, so it can sit on top of
+    /// other content.
+    pub fn opaque(mut self) -> Self {
+        self.opaque = true;
+        self
+    }
+
+    /// Add a widget
+    pub fn add<W: Widget>(&mut self, widget: W) -> WidgetId {
+        self.widgets.push(Box::new(widget));
+        WidgetId(self.widgets.len() - 1)
+    }
+
+    /// Get a widget by its handle
+    pub fn get<W: Widget>(&self, id: WidgetId) -> Option<&W> {
+        let widget: &dyn Any = self.widgets.get(id.0)?.as_ref();
+        widget.downcast_ref()
+    }
+
+    /// Get a widget by its handle, e.g. to change its value
+    pub fn get_mut<W: Widget>(&mut self, id: WidgetId) -> Option<&mut W> {
+        let widget: &mut dyn Any = self.widgets.get_mut(id.0)?.as_mut();
+        widget.downcast_mut()
+    }
+
+    /// Route a touch event to the widget under the finger
+    ///
+    /// Returns what the widget reported, if anything. Callbacks registered
+    /// on the widget have already run.
+    pub fn handle_event(&mut self, event: &TouchEvent) -> Option<(WidgetId, WidgetEvent)> {
+        let id = event.id();
+        let captured = self.captures.iter().flatten().find(|c| c.0 == id);
+
+        let index = match (event, captured) {
+            (TouchEvent::Down { x, y, .. }, None) => {
+                let point = Point::new(*x as i32, *y as i32);
+                // Topmost (last added) widget wins
+                let index = self
+                    .widgets
+                    .iter()
+                    .rposition(|w| w.base().is_enabled() && w.base().bounds().contains(point))?;
+                if let Some(slot) = self.captures.iter_mut().find(|c| c.is_none()) {
+                    *slot = Some((id, index));
+                }
+                index
+            }
+            (TouchEvent::Move { .. } | TouchEvent::Up { .. }, Some(&(_, index))) => index,
+            _ => return None,
+        };
+
+        if matches!(event, TouchEvent::Up { .. }) {
+            self.release(id);
+            self.released = Some((id, index));
+        }
+
+        // Disabled while pressed: drop the finger
+        if !self.widgets[index].base().is_enabled() {
+            self.release(id);
+            return None;
+        }
+        self.widgets[index]
+            .touch(event)
+            .map(|e| (WidgetId(index), e))
+    }
+
+    /// Pass the release velocity of finger `id` from
+    /// [`Axs5106l::motion`](crate::axs5106l::Axs5106l::motion) to the widget
+    /// it just let go of
+    pub fn fling(&mut self, id: u8, motion: &Motion) {
+        if let Some((released, index)) = self.released.take()
+            && released == id
+        {
+            self.widgets[index].fling(motion);
+        }
+    }
+
+    /// Advance widget animations
+    pub fn update(&mut self, now_ms: u64) {
+        self.widgets.iter_mut().for_each(|w| w.update(now_ms));
+    }
+
+    /// Draw every widget that changed
+    pub fn draw<D>(&mut self, target: &mut D) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = Rgb565>,
+    {
+        with_canvas(target, |canvas| self.paint(canvas))
+    }
+
+    /// Draw every widget that changed on a canvas
+    pub fn paint(&mut self, canvas: &mut Canvas<'_>) -> Result<(), DrawError> {
+        if self.opaque && !self.cleared {
+            canvas.clear(theme().background)?;
+            self.cleared = true;
+        }
+        for widget in self.widgets.iter_mut() {
+            if widget.base().is_dirty() {
+                widget.draw(canvas)?;
+                widget.base_mut().mark_drawn();
+            }
+        }
+        Ok(())
+    }
+
+    /// Redraw every widget on the next [`draw`](Self::draw)
+    pub fn invalidate(&mut self) {
+        self.cleared = false;
+        self.widgets.iter_mut().for_each(|w| w.invalidate());
+    }
+
+    fn release(&mut self, id: u8) {
+        for capture in self.captures.iter_mut() {
+            if capture.is_some_and(|c| c.0 == id) {
+                *capture = None;
+            }
+        }
+    }
+}
+
+/// Draw a single line of text, vertically centered in `area` and cut off
+/// with "..." if it is too wide
+fn draw_text(
+    canvas: &mut Canvas<'_>,
+    text: &str,
+    area: &Rectangle,
+    alignment: Alignment,
+    font: Font,
+    color: Rgb565,
+) -> Result<(), DrawError> {
+    TextBox::new(text, *area, font, color)
+        .with_alignment(alignment)
+        .draw(canvas)
+}
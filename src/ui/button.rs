@@ -0,0 +1,100 @@
+//! Push button
+
+use alloc::{boxed::Box, string::String};
+
+use embedded_graphics::{
+    prelude::*,
+    primitives::{PrimitiveStyle, Rectangle, RoundedRectangle},
+    text::Alignment,
+};
+
+use crate::axs5106l::TouchEvent;
+
+use super::{Canvas, DrawError, Widget, WidgetBase, WidgetEvent, draw_text, theme};
+
+/// Button that reports a click when released inside its bounds
+pub struct Button {
+    base: WidgetBase,
+    text: String,
+    on_click: Option<Box<dyn FnMut()>>,
+}
+
+impl Button {
+    /// Create a button with a centered caption
+    pub fn new(bounds: Rectangle, text: &str) -> Self {
+        Self {
+            base: WidgetBase::new(bounds),
+            text: String::from(text),
+            on_click: None,
+        }
+    }
+
+    /// Call `f` on every click
+    pub fn on_click(mut self, f: impl FnMut() + 'static) -> Self {
+        self.on_click = Some(Box::new(f));
+        self
+    }
+
+    /// Change the caption
+    pub fn set_text(&mut self, text: &str) {
+        if self.text != text {
+            self.text.clear();
+            self.text.push_str(text);
+            self.base.invalidate();
+        }
+    }
+}
+
+impl Widget for Button {
+    fn base(&self) -> &WidgetBase {
+        &self.base
+    }
+
+    fn base_mut(&mut self) -> &mut WidgetBase {
+        &mut self.base
+    }
+
+    fn draw(&mut self, canvas: &mut Canvas<'_>) -> Result<(), DrawError> {
+        let theme = theme();
+        let bounds = self.base.bounds();
+        canvas.fill_solid(&bounds, theme.background)?;
+        let radius = Size::new(theme.radius, theme.radius);
+        RoundedRectangle::with_equal_corners(bounds, radius)
+            .into_styled(PrimitiveStyle::with_fill(theme.fill(&self.base)))
+            .draw(canvas)?;
+        let color = theme.active(&self.base, theme.text);
+        draw_text(
+            canvas,
+            &self.text,
+            &bounds,
+            Alignment::Center,
+            theme.font,
+            color,
+        )
+    }
+
+    fn touch(&mut self, event: &TouchEvent) -> Option<WidgetEvent> {
+        let (x, y) = event.position()?;
+        let inside = self.base.bounds().contains(Point::new(x as i32, y as i32));
+
+        match event {
+            TouchEvent::Down { .. } | TouchEvent::Move { .. } => {
+                // Sliding off the button cancels the click
+                self.base.set_pressed(inside);
+                None
+            }
+            TouchEvent::Up { .. } => {
+                let clicked = self.base.is_pressed() && inside;
+                self.base.set_pressed(false);
+                if !clicked {
+                    return None;
+                }
+                if let Some(f) = self.on_click.as_mut() {
+                    f();
+                }
+                Some(WidgetEvent::Clicked)
+            }
+            _ => None,
+        }
+    }
+}
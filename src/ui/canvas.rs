@@ -0,0 +1,112 @@
+//! Type-erased draw target for widgets
+//!
+//! `DrawTarget` has generic methods, so `dyn DrawTarget` doesn't exist.
+//! [`Canvas`] wraps any RGB565 draw target behind an object-safe trait and
+//! keeps the target's error aside, so widget code is not generic over the
+//! display type.
+
+use embedded_graphics::{pixelcolor::Rgb565, prelude::*, primitives::Rectangle};
+
+/// Drawing failed, the target's error is returned by the caller that
+/// created the canvas
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct DrawError;
+
+/// Object-safe subset of `DrawTarget`
+trait Surface {
+    fn draw_pixels(&mut self, pixels: &mut dyn Iterator<Item = Pixel<Rgb565>>) -> bool;
+    fn fill_solid(&mut self, area: &Rectangle, color: Rgb565) -> bool;
+    fn size(&self) -> Size;
+}
+
+/// Surface over a concrete draw target, holding its first error
+struct Adapter<'d, D: DrawTarget<Color = Rgb565>> {
+    target: &'d mut D,
+    error: Option<D::Error>,
+}
+
+impl<D: DrawTarget<Color = Rgb565>> Adapter<'_, D> {
+    fn check(&mut self, result: Result<(), D::Error>) -> bool {
+        match result {
+            Ok(()) => true,
+            Err(e) => {
+                self.error.get_or_insert(e);
+                false
+            }
+        }
+    }
+}
+
+impl<D: DrawTarget<Color = Rgb565>> Surface for Adapter<'_, D> {
+    fn draw_pixels(&mut self, pixels: &mut dyn Iterator<Item = Pixel<Rgb565>>) -> bool {
+        let result = self.target.draw_iter(pixels);
+        self.check(result)
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Rgb565) -> bool {
+        let result = self.target.fill_solid(area, color);
+        self.check(result)
+    }
+
+    fn size(&self) -> Size {
+        self.target.bounding_box().size
+    }
+}
+
+/// Draw target handed to widgets
+pub struct Canvas<'a> {
+    surface: &'a mut dyn Surface,
+}
+
+/// Run `f` with a canvas drawing on `target`
+///
+/// Returns the first error of `target`, if `f` hit one.
+pub fn with_canvas<D, R>(
+    target: &mut D,
+    f: impl FnOnce(&mut Canvas<'_>) -> Result<R, DrawError>,
+) -> Result<(), D::Error>
+where
+    D: DrawTarget<Color = Rgb565>,
+{
+    let mut adapter = Adapter {
+        target,
+        error: None,
+    };
+    let _ = f(&mut Canvas {
+        surface: &mut adapter,
+    });
+    match adapter.error {
+        Some(e) => Err(e),
+        None => Ok(()),
+    }
+}
+
+impl OriginDimensions for Canvas<'_> {
+    fn size(&self) -> Size {
+        self.surface.size()
+    }
+}
+
+impl DrawTarget for Canvas<'_> {
+    type Color = Rgb565;
+    type Error = DrawError;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        if self.surface.draw_pixels(&mut pixels.into_iter()) {
+            Ok(())
+        } else {
+            Err(DrawError)
+        }
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        if self.surface.fill_solid(area, color) {
+            Ok(())
+        } else {
+            Err(DrawError)
+        }
+    }
+}
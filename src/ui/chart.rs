@@ -0,0 +1,422 @@
+//! Line chart of the latest readings of a sensor
+
+use alloc::{format, string::String};
+
+use embedded_graphics::{
+    pixelcolor::Rgb565,
+    prelude::*,
+    primitives::{Line, PrimitiveStyle, Rectangle},
+    text::Alignment,
+};
+
+use super::{Canvas, DrawError, Theme, Widget, WidgetBase, draw_text, theme};
+
+/// Characters of the value labels left of the plot
+const LABEL_CHARS: u32 = 5;
+/// Space added above and below the data, as a fraction of its span
+const MARGIN: f32 = 0.1;
+/// The axis shrinks once the data fills less than this fraction of it
+const SHRINK: f32 = 0.33;
+
+/// Part of the plot that has to be redrawn
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Redraw {
+    None,
+    /// Segments from this sample on, the rest didn't move
+    From(usize),
+    All,
+}
+
+/// Chart of the last `N` samples
+///
+/// Samples go into a ring buffer, so memory use is fixed. With
+/// [`with_interval_ms`](Self::with_interval_ms) the readings pushed during
+/// each interval are averaged into one sample, so `N` samples cover
+/// `N` x interval, e.g. 150 samples of 2 s for the last 5 minutes.
+///
+/// The value axis follows the data unless fixed with
+/// [`with_range`](Self::with_range). While the buffer fills up only the new
+/// segment is drawn; once full, the plot scrolls and is redrawn per sample.
+/// Without title and axes the chart is a bare sparkline.
+pub struct Chart<const N: usize> {
+    base: WidgetBase,
+    /// Theme accent if None
+    color: Option<Rgb565>,
+    title: String,
+    unit: String,
+    axes: bool,
+    samples: [f32; N],
+    /// Next slot to write
+    head: usize,
+    len: usize,
+    interval_ms: u64,
+    /// Start, sum and count of the readings averaged into the next sample
+    bucket: Option<(u64, f32, u32)>,
+    latest: Option<f32>,
+    fixed: Option<(f32, f32)>,
+    min_span: f32,
+    range: (f32, f32),
+    header_dirty: bool,
+    redraw: Redraw,
+}
+
+impl<const N: usize> Chart<N> {
+    /// Create an empty sparkline
+    pub fn new(bounds: Rectangle) -> Self {
+        Self {
+            base: WidgetBase::new(bounds),
+            color: None,
+            title: String::new(),
+            unit: String::new(),
+            axes: false,
+            samples: [0.0; N],
+            head: 0,
+            len: 0,
+            interval_ms: 0,
+            bucket: None,
+            latest: None,
+            fixed: None,
+            min_span: 1.0,
+            range: (0.0, 1.0),
+            header_dirty: true,
+            redraw: Redraw::All,
+        }
+    }
+
+    /// Draw the line in `color` instead of the theme accent
+    pub fn with_color(mut self, color: Rgb565) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    /// Show a title and the latest reading with `unit` above the plot
+    pub fn with_title(mut self, title: &str, unit: &str) -> Self {
+        self.title = String::from(title);
+        self.unit = String::from(unit);
+        self
+    }
+
+    /// Show value labels left of and a time span below the plot
+    pub fn with_axes(mut self, axes: bool) -> Self {
+        self.axes = axes;
+        self
+    }
+
+    /// Average the readings of each `interval_ms` into one sample
+    pub fn with_interval_ms(mut self, interval_ms: u64) -> Self {
+        self.interval_ms = interval_ms;
+        self
+    }
+
+    /// Use a fixed value axis instead of following the data
+    pub fn with_range(mut self, min: f32, max: f32) -> Self {
+        self.fixed = Some((min, max));
+        self.range = (min, max);
+        self
+    }
+
+    /// Smallest span of the automatic value axis, so noise on a steady
+    /// reading doesn't fill the whole plot
+    pub fn with_min_span(mut self, min_span: f32) -> Self {
+        self.min_span = min_span;
+        self
+    }
+
+    /// Add a reading taken at `now_ms`
+    pub fn push(&mut self, now_ms: u64, value: f32) {
+        if self.latest != Some(value) {
+            self.latest = Some(value);
+            self.header_dirty = true;
+            self.base.invalidate();
+        }
+
+        if self.interval_ms == 0 {
+            self.append(value);
+            return;
+        }
+        let (start, sum, count) = self.bucket.get_or_insert((now_ms, 0.0, 0));
+        *sum += value;
+        *count += 1;
+        if now_ms.saturating_sub(*start) >= self.interval_ms {
+            let average = *sum / *count as f32;
+            self.bucket = None;
+            self.append(average);
+        }
+    }
+
+    /// Latest reading
+    pub fn latest(&self) -> Option<f32> {
+        self.latest
+    }
+
+    /// Number of samples in the buffer
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether no sample was taken yet
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Samples from the oldest to the newest
+    pub fn samples(&self) -> impl Iterator<Item = f32> + '_ {
+        (0..self.len).map(|i| self.sample(i))
+    }
+
+    /// Drop all samples
+    pub fn clear(&mut self) {
+        self.head = 0;
+        self.len = 0;
+        self.bucket = None;
+        self.latest = None;
+        self.header_dirty = true;
+        self.redraw = Redraw::All;
+        self.base.invalidate();
+    }
+
+    fn sample(&self, index: usize) -> f32 {
+        self.samples[(self.head + N - self.len + index) % N]
+    }
+
+    fn append(&mut self, value: f32) {
+        if N == 0 {
+            return;
+        }
+        self.samples[self.head] = value;
+        self.head = (self.head + 1) % N;
+        let scrolled = self.len == N;
+        self.len = (self.len + 1).min(N);
+
+        let rescaled = self.rescale();
+        self.redraw = match self.redraw {
+            _ if scrolled || rescaled => Redraw::All,
+            Redraw::None => Redraw::From(self.len.saturating_sub(2)),
+            redraw => redraw,
+        };
+        self.base.invalidate();
+    }
+
+    /// Fit the value axis to the data, returns true if it changed
+    fn rescale(&mut self) -> bool {
+        if self.fixed.is_some() {
+            return false;
+        }
+        let (low, high) = self
+            .samples()
+            .fold((f32::MAX, f32::MIN), |(lo, hi), v| (lo.min(v), hi.max(v)));
+        let span = (high - low).max(self.min_span);
+        let (current_low, current_high) = self.range;
+        let current_span = current_high - current_low;
+
+        let outside = low < current_low || high > current_high;
+        let too_wide = span < current_span * SHRINK;
+        if !outside && !too_wide && self.len > 1 {
+            return false;
+        }
+        let center = (low + high) / 2.0;
+        let half = span * (0.5 + MARGIN);
+        self.range = (center - half, center + half);
+        true
+    }
+
+    fn header_area(&self, theme: &Theme) -> Option<Rectangle> {
+        let bounds = self.base.bounds();
+        let size = Size::new(bounds.size.width, header_height(theme));
+        (!self.title.is_empty()).then(|| Rectangle::new(bounds.top_left, size))
+    }
+
+    /// Area the line is drawn in
+    fn plot_area(&self, theme: &Theme) -> Rectangle {
+        let bounds = self.base.bounds();
+        let top = if self.title.is_empty() {
+            0
+        } else {
+            header_height(theme)
+        };
+        let (left, bottom) = if self.axes {
+            (labels_width(theme), footer_height(theme))
+        } else {
+            (0, 0)
+        };
+        Rectangle::new(
+            bounds.top_left + Point::new(left as i32, top as i32),
+            Size::new(
+                bounds.size.width.saturating_sub(left),
+                bounds.size.height.saturating_sub(top + bottom),
+            ),
+        )
+    }
+
+    fn point(&self, plot: &Rectangle, index: usize) -> Point {
+        let width = plot.size.width.saturating_sub(1) as usize;
+        let height = plot.size.height.saturating_sub(1) as f32;
+        let x = plot.top_left.x + (index * width / N.saturating_sub(1).max(1)) as i32;
+        let (low, high) = self.range;
+        let fraction = ((self.sample(index) - low) / (high - low)).clamp(0.0, 1.0);
+        let y = plot.top_left.y + (height * (1.0 - fraction)) as i32;
+        Point::new(x, y)
+    }
+
+    fn draw_header(
+        &self,
+        canvas: &mut Canvas<'_>,
+        theme: &Theme,
+        area: &Rectangle,
+    ) -> Result<(), DrawError> {
+        canvas.fill_solid(area, theme.background)?;
+        let (font, color) = (theme.font, theme.text);
+        draw_text(canvas, &self.title, area, Alignment::Left, font, color)?;
+        if let Some(value) = self.latest {
+            let value = format!("{:.2} {}", value, self.unit);
+            let color = self.color.unwrap_or(theme.accent);
+            draw_text(canvas, &value, area, Alignment::Right, font, color)?;
+        }
+        Ok(())
+    }
+
+    fn draw_axes(
+        &self,
+        canvas: &mut Canvas<'_>,
+        theme: &Theme,
+        plot: &Rectangle,
+    ) -> Result<(), DrawError> {
+        let bounds = self.base.bounds();
+        let (font, color) = (theme.small_font, theme.muted);
+        let labels_width = labels_width(theme);
+        let footer_height = footer_height(theme);
+        let labels = Rectangle::new(
+            Point::new(bounds.top_left.x, plot.top_left.y),
+            Size::new(labels_width, plot.size.height + footer_height),
+        );
+        canvas.fill_solid(&labels, theme.background)?;
+        let footer = Rectangle::new(
+            Point::new(plot.top_left.x, plot.top_left.y + plot.size.height as i32),
+            Size::new(plot.size.width, footer_height),
+        );
+        canvas.fill_solid(&footer, theme.background)?;
+
+        // Value labels at the top and bottom of the plot
+        let (low, high) = self.range;
+        let line = font.line_height();
+        let label = |y: i32| {
+            Rectangle::new(
+                Point::new(bounds.top_left.x, y),
+                Size::new(labels_width.saturating_sub(3), line),
+            )
+        };
+        let bottom = plot.top_left.y + plot.size.height as i32 - line as i32;
+        draw_text(
+            canvas,
+            &format!("{:.1}", high),
+            &label(plot.top_left.y),
+            Alignment::Right,
+            font,
+            color,
+        )?;
+        draw_text(
+            canvas,
+            &format!("{:.1}", low),
+            &label(bottom),
+            Alignment::Right,
+            font,
+            color,
+        )?;
+
+        // Time covered by a full buffer, to now at the right
+        let span_s = self.interval_ms * N as u64 / 1_000;
+        if span_s > 0 {
+            let span = if span_s >= 120 {
+                format!("-{} min", span_s / 60)
+            } else {
+                format!("-{} s", span_s)
+            };
+            draw_text(canvas, &span, &footer, Alignment::Left, font, color)?;
+        }
+        draw_text(canvas, "now", &footer, Alignment::Right, font, color)
+    }
+
+    fn draw_plot(
+        &self,
+        canvas: &mut Canvas<'_>,
+        theme: &Theme,
+        from: usize,
+    ) -> Result<(), DrawError> {
+        let plot = self.plot_area(theme);
+        if from == 0 {
+            canvas.fill_solid(&plot, theme.background)?;
+            if self.axes {
+                self.draw_axes(canvas, theme, &plot)?;
+            }
+            // Mid line as a reference
+            let mid = plot.center().y;
+            let right = plot.top_left.x + plot.size.width as i32 - 1;
+            Line::new(Point::new(plot.top_left.x, mid), Point::new(right, mid))
+                .into_styled(PrimitiveStyle::with_stroke(theme.surface, 1))
+                .draw(canvas)?;
+        }
+
+        let color = self.color.unwrap_or(theme.accent);
+        let style = PrimitiveStyle::with_stroke(color, 1);
+        if self.len == 1 {
+            return canvas.fill_solid(
+                &Rectangle::new(self.point(&plot, 0), Size::new(1, 1)),
+                color,
+            );
+        }
+        for index in from..self.len.saturating_sub(1) {
+            Line::new(self.point(&plot, index), self.point(&plot, index + 1))
+                .into_styled(style)
+                .draw(canvas)?;
+        }
+        Ok(())
+    }
+}
+
+impl<const N: usize> Widget for Chart<N> {
+    fn base(&self) -> &WidgetBase {
+        &self.base
+    }
+
+    fn base_mut(&mut self) -> &mut WidgetBase {
+        &mut self.base
+    }
+
+    fn draw(&mut self, canvas: &mut Canvas<'_>) -> Result<(), DrawError> {
+        let theme = theme();
+        if self.header_dirty
+            && let Some(area) = self.header_area(&theme)
+        {
+            self.draw_header(canvas, &theme, &area)?;
+        }
+        match self.redraw {
+            Redraw::None => {}
+            Redraw::From(from) => self.draw_plot(canvas, &theme, from)?,
+            Redraw::All => self.draw_plot(canvas, &theme, 0)?,
+        }
+        self.header_dirty = false;
+        self.redraw = Redraw::None;
+        Ok(())
+    }
+
+    fn invalidate(&mut self) {
+        self.header_dirty = true;
+        self.redraw = Redraw::All;
+        self.base.invalidate();
+    }
+}
+
+/// Height of the title row
+fn header_height(theme: &Theme) -> u32 {
+    theme.font.line_height() + 2
+}
+
+/// Height of the time labels below the plot
+fn footer_height(theme: &Theme) -> u32 {
+    theme.small_font.line_height() + 1
+}
+
+/// Width of the value labels left of the plot
+fn labels_width(theme: &Theme) -> u32 {
+    theme.small_font.advance('0') * LABEL_CHARS
+}
@@ -0,0 +1,131 @@
+//! Check box with a caption
+
+use alloc::{boxed::Box, string::String};
+
+use embedded_graphics::{
+    prelude::*,
+    primitives::{Polyline, PrimitiveStyle, Rectangle},
+    text::Alignment,
+};
+
+use crate::axs5106l::TouchEvent;
+
+use super::{Canvas, DrawError, Widget, WidgetBase, WidgetEvent, draw_text, theme};
+
+/// Box on the left, caption on the right; tapping either toggles it
+pub struct Checkbox {
+    base: WidgetBase,
+    text: String,
+    checked: bool,
+    on_change: Option<Box<dyn FnMut(bool)>>,
+}
+
+impl Checkbox {
+    /// Create a check box, the box is as large as `bounds` is high
+    pub fn new(bounds: Rectangle, text: &str, checked: bool) -> Self {
+        Self {
+            base: WidgetBase::new(bounds),
+            text: String::from(text),
+            checked,
+            on_change: None,
+        }
+    }
+
+    /// Call `f` with the new state whenever the user taps the check box
+    pub fn on_change(mut self, f: impl FnMut(bool) + 'static) -> Self {
+        self.on_change = Some(Box::new(f));
+        self
+    }
+
+    /// Get the state
+    pub fn is_checked(&self) -> bool {
+        self.checked
+    }
+
+    /// Set the state without calling the callback
+    pub fn set_checked(&mut self, checked: bool) {
+        if self.checked != checked {
+            self.checked = checked;
+            self.base.invalidate();
+        }
+    }
+}
+
+impl Widget for Checkbox {
+    fn base(&self) -> &WidgetBase {
+        &self.base
+    }
+
+    fn base_mut(&mut self) -> &mut WidgetBase {
+        &mut self.base
+    }
+
+    fn draw(&mut self, canvas: &mut Canvas<'_>) -> Result<(), DrawError> {
+        let theme = theme();
+        let bounds = self.base.bounds();
+        let side = bounds.size.height;
+        let gap = theme.spacing;
+        canvas.fill_solid(&bounds, theme.background)?;
+
+        let check_box = Rectangle::new(bounds.top_left, Size::new(side, side));
+        canvas.fill_solid(&check_box, theme.fill(&self.base))?;
+
+        if self.checked {
+            let s = side as i32;
+            let origin = bounds.top_left;
+            let mark = [
+                origin + Point::new(s / 5, s / 2),
+                origin + Point::new(s * 2 / 5, s * 3 / 4),
+                origin + Point::new(s * 4 / 5, s / 4),
+            ];
+            Polyline::new(&mark)
+                .into_styled(PrimitiveStyle::with_stroke(
+                    theme.active(&self.base, theme.accent),
+                    (side / 8).max(2),
+                ))
+                .draw(canvas)?;
+        }
+
+        let caption = Rectangle::new(
+            bounds.top_left + Point::new((side + gap) as i32, 0),
+            Size::new(
+                bounds.size.width.saturating_sub(side + gap),
+                bounds.size.height,
+            ),
+        );
+        let color = theme.active(&self.base, theme.text);
+        draw_text(
+            canvas,
+            &self.text,
+            &caption,
+            Alignment::Left,
+            theme.font,
+            color,
+        )
+    }
+
+    fn touch(&mut self, event: &TouchEvent) -> Option<WidgetEvent> {
+        let (x, y) = event.position()?;
+        let inside = self.base.bounds().contains(Point::new(x as i32, y as i32));
+
+        match event {
+            TouchEvent::Down { .. } | TouchEvent::Move { .. } => {
+                self.base.set_pressed(inside);
+                None
+            }
+            TouchEvent::Up { .. } => {
+                let tapped = self.base.is_pressed() && inside;
+                self.base.set_pressed(false);
+                if !tapped {
+                    return None;
+                }
+                self.set_checked(!self.checked);
+                if let Some(f) = self.on_change.as_mut() {
+                    f(self.checked);
+                }
+                Some(WidgetEvent::Toggled(self.checked))
+            }
+            _ => None,
+        }
+    }
+}
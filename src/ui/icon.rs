@@ -0,0 +1,54 @@
+//! Image from flash
+
+use embedded_graphics::{image::Image, prelude::*, primitives::Rectangle};
+
+use crate::qoi::Qoi;
+
+use super::{Canvas, DrawError, Widget, WidgetBase, theme};
+
+/// QOI image centered in its bounds
+///
+/// Transparent pixels show the theme background and partly transparent
+/// edges are blended with it, so icons look right in both themes.
+pub struct Icon {
+    base: WidgetBase,
+    image: Qoi<'static>,
+}
+
+impl Icon {
+    /// Create an icon showing `image`
+    pub fn new(bounds: Rectangle, image: Qoi<'static>) -> Self {
+        Self {
+            base: WidgetBase::new(bounds),
+            image,
+        }
+    }
+
+    /// Show another image, e.g. a different battery level
+    pub fn set_image(&mut self, image: Qoi<'static>) {
+        self.image = image;
+        self.base.invalidate();
+    }
+}
+
+impl Widget for Icon {
+    fn base(&self) -> &WidgetBase {
+        &self.base
+    }
+
+    fn base_mut(&mut self) -> &mut WidgetBase {
+        &mut self.base
+    }
+
+    fn draw(&mut self, canvas: &mut Canvas<'_>) -> Result<(), DrawError> {
+        let theme = theme();
+        let bounds = self.base.bounds();
+        canvas.fill_solid(&bounds, theme.background)?;
+        let image = self.image.with_background(theme.background);
+        let size = image.size();
+        let top_left = bounds.center() - Point::new(size.width as i32 / 2, size.height as i32 / 2);
+        // Images larger than the bounds are cut off
+        Image::new(&image, top_left).draw(&mut canvas.clipped(&bounds))?;
+        Ok(())
+    }
+}
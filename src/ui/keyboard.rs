@@ -0,0 +1,345 @@
+//! On-screen keyboard
+
+use alloc::boxed::Box;
+
+use embedded_graphics::{
+    prelude::*,
+    primitives::{PrimitiveStyle, Rectangle, RoundedRectangle},
+    text::Alignment,
+};
+
+use crate::axs5106l::TouchEvent;
+
+use super::{Canvas, DrawError, Theme, Widget, WidgetBase, WidgetEvent, draw_text, theme};
+
+/// Keys per row on the widest rows
+const COLUMNS: u32 = 10;
+/// Space around each key
+const GAP: u32 = 1;
+
+/// Character rows of the letter layer, the special keys are added around them
+const LETTERS: [&str; 3] = ["qwertyuiop", "asdfghjkl", "zxcvbnm"];
+/// Character rows of the number layer
+const NUMBERS: [&str; 3] = ["1234567890", "-/:;()$&@\"", ".,?!'"];
+/// Character rows of the symbol layer
+const SYMBOLS: [&str; 3] = ["[]{}#%^*+=", "_\\|~<>`'\"", ".,?!'"];
+
+/// Key reported by a [`Keyboard`]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Key {
+    /// A character, shift already applied
+    Char(char),
+    /// Delete the character before the cursor
+    Backspace,
+    /// Done entering text
+    Enter,
+}
+
+/// Set of keys shown on the keyboard
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Layer {
+    Letters,
+    Numbers,
+    Symbols,
+}
+
+impl Layer {
+    fn rows(self) -> &'static [&'static str; 3] {
+        match self {
+            Layer::Letters => &LETTERS,
+            Layer::Numbers => &NUMBERS,
+            Layer::Symbols => &SYMBOLS,
+        }
+    }
+}
+
+/// What a key does
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Action {
+    Char(char),
+    Shift,
+    /// Switch between letters and numbers
+    Layer,
+    /// Switch between numbers and symbols
+    Symbols,
+    Space,
+    Backspace,
+    Enter,
+}
+
+/// Keyboard with letter, number and symbol layers
+///
+/// Keys light up while pressed and fire when released; sliding the finger
+/// moves the highlight to the key under it. Shift applies to the next
+/// letter only. Connect it to a [`TextField`](super::TextField) by passing
+/// the reported [`Key`]s to [`TextField::key`](super::TextField::key).
+pub struct Keyboard {
+    base: WidgetBase,
+    layer: Layer,
+    shift: bool,
+    pressed: Option<usize>,
+    /// Pressed key at the last draw, None after a change of labels
+    drawn: Option<Option<usize>>,
+    drawn_enabled: bool,
+    on_key: Option<Box<dyn FnMut(Key)>>,
+}
+
+impl Keyboard {
+    /// Create a keyboard of four rows filling `bounds`
+    ///
+    /// Fits the 172 px wide display with keys of 17 px.
+    pub fn new(bounds: Rectangle) -> Self {
+        Self {
+            base: WidgetBase::new(bounds),
+            layer: Layer::Letters,
+            shift: false,
+            pressed: None,
+            drawn: None,
+            drawn_enabled: true,
+            on_key: None,
+        }
+    }
+
+    /// Call `f` with every key the user types
+    pub fn on_key(mut self, f: impl FnMut(Key) + 'static) -> Self {
+        self.on_key = Some(Box::new(f));
+        self
+    }
+
+    /// Whether the next letter is upper case
+    pub fn is_shifted(&self) -> bool {
+        self.shift
+    }
+
+    /// Area and action of the `index`th key, None past the last key
+    fn key(&self, index: usize) -> Option<(Rectangle, Action)> {
+        let bounds = self.base.bounds();
+        let unit = bounds.size.width / COLUMNS;
+        let height = bounds.size.height / 4;
+        let rows = self.layer.rows();
+        let left = bounds.top_left.x + ((bounds.size.width - unit * COLUMNS) / 2) as i32;
+
+        // Row 0 and 1: characters, centered; row 2: characters between two
+        // wide keys; row 3: layer, space, '.' and enter
+        let mut index = index;
+        let (row, x, width, action) = 'found: {
+            for (row, chars) in rows[..2].iter().enumerate() {
+                let count = chars.len();
+                if index < count {
+                    let x = left + ((COLUMNS - count as u32) * unit / 2) as i32;
+                    let c = chars.as_bytes()[index] as char;
+                    break 'found (row, x + (index as u32 * unit) as i32, unit, Action::Char(c));
+                }
+                index -= count;
+            }
+
+            let chars = rows[2];
+            let wide = unit * 3 / 2;
+            let narrow = (unit * COLUMNS - 2 * wide) / chars.len() as u32;
+            let middle = left + ((unit * COLUMNS - narrow * chars.len() as u32) / 2) as i32;
+            let first = match self.layer {
+                Layer::Letters => Action::Shift,
+                Layer::Numbers | Layer::Symbols => Action::Symbols,
+            };
+            match index {
+                0 => break 'found (2, left, wide, first),
+                i if i <= chars.len() => {
+                    let c = chars.as_bytes()[i - 1] as char;
+                    let x = middle + ((i as u32 - 1) * narrow) as i32;
+                    break 'found (2, x, narrow, Action::Char(c));
+                }
+                _ => {}
+            }
+            index -= chars.len() + 1;
+            if index == 0 {
+                let x = left + (unit * COLUMNS - wide) as i32;
+                break 'found (2, x, wide, Action::Backspace);
+            }
+            index -= 1;
+
+            let (x, width, action) = match index {
+                0 => (0, 2, Action::Layer),
+                1 => (2, 5, Action::Space),
+                2 => (7, 1, Action::Char('.')),
+                3 => (8, 2, Action::Enter),
+                _ => return None,
+            };
+            (3, left + (x * unit) as i32, width * unit, action)
+        };
+
+        let area = Rectangle::new(
+            Point::new(x, bounds.top_left.y + (row as u32 * height) as i32),
+            Size::new(width, height),
+        );
+        Some((area, action))
+    }
+
+    fn key_at(&self, point: Point) -> Option<usize> {
+        (0..)
+            .map_while(|i| self.key(i).map(|k| (i, k)))
+            .find(|(_, (area, _))| area.contains(point))
+            .map(|(i, _)| i)
+    }
+
+    fn draw_key(
+        &self,
+        canvas: &mut Canvas<'_>,
+        theme: &Theme,
+        index: usize,
+    ) -> Result<(), DrawError> {
+        let Some((area, action)) = self.key(index) else {
+            return Ok(());
+        };
+        canvas.fill_solid(&area, theme.background)?;
+
+        let fill = if self.pressed == Some(index) {
+            theme.pressed
+        } else if action == Action::Shift && self.shift {
+            theme.active(&self.base, theme.accent)
+        } else {
+            theme.surface
+        };
+        let key = area.offset(-(GAP as i32));
+        // Keys are small, half the usual rounding
+        let radius = Size::new(theme.radius / 2, theme.radius / 2);
+        RoundedRectangle::with_equal_corners(key, radius)
+            .into_styled(PrimitiveStyle::with_fill(fill))
+            .draw(canvas)?;
+
+        let mut buffer = [0u8; 4];
+        let label = match action {
+            Action::Char(c) => {
+                let c = if self.shift {
+                    c.to_ascii_uppercase()
+                } else {
+                    c
+                };
+                &*c.encode_utf8(&mut buffer)
+            }
+            Action::Shift => "Aa",
+            Action::Layer if self.layer == Layer::Letters => "123",
+            Action::Layer => "abc",
+            Action::Symbols if self.layer == Layer::Numbers => "#+=",
+            Action::Symbols => "123",
+            Action::Space => "space",
+            Action::Backspace => "<-",
+            Action::Enter => "OK",
+        };
+        let color = theme.active(&self.base, theme.text);
+        draw_text(canvas, label, &key, Alignment::Center, theme.font, color)
+    }
+
+    fn set_pressed_key(&mut self, index: Option<usize>) {
+        if self.pressed != index {
+            self.pressed = index;
+            self.base.set_pressed(index.is_some());
+            self.base.invalidate();
+        }
+    }
+
+    /// Change labels, redrawing every key
+    fn relabel(&mut self, layer: Layer, shift: bool) {
+        if self.layer != layer || self.shift != shift {
+            self.layer = layer;
+            self.shift = shift;
+            Widget::invalidate(self);
+        }
+    }
+}
+
+impl Widget for Keyboard {
+    fn base(&self) -> &WidgetBase {
+        &self.base
+    }
+
+    fn base_mut(&mut self) -> &mut WidgetBase {
+        &mut self.base
+    }
+
+    fn draw(&mut self, canvas: &mut Canvas<'_>) -> Result<(), DrawError> {
+        let theme = theme();
+        if self.drawn_enabled != self.base.is_enabled() {
+            self.drawn = None;
+        }
+        match self.drawn {
+            // Only the highlight moved
+            Some(previous) => {
+                for index in [previous, self.pressed].into_iter().flatten() {
+                    self.draw_key(canvas, &theme, index)?;
+                }
+            }
+            None => {
+                canvas.fill_solid(&self.base.bounds(), theme.background)?;
+                for index in (0..).take_while(|&i| self.key(i).is_some()) {
+                    self.draw_key(canvas, &theme, index)?;
+                }
+            }
+        }
+        self.drawn = Some(self.pressed);
+        self.drawn_enabled = self.base.is_enabled();
+        Ok(())
+    }
+
+    fn invalidate(&mut self) {
+        self.drawn = None;
+        self.base.invalidate();
+    }
+
+    fn touch(&mut self, event: &TouchEvent) -> Option<WidgetEvent> {
+        let (x, y) = event.position()?;
+        let key = self.key_at(Point::new(x as i32, y as i32));
+
+        match event {
+            TouchEvent::Down { .. } | TouchEvent::Move { .. } => {
+                self.set_pressed_key(key);
+                None
+            }
+            TouchEvent::Up { .. } => {
+                let pressed = self.pressed;
+                self.set_pressed_key(None);
+                let (_, action) = self.key(pressed.filter(|&p| key == Some(p))?)?;
+
+                let key = match action {
+                    Action::Char(c) => {
+                        let c = if self.shift {
+                            c.to_ascii_uppercase()
+                        } else {
+                            c
+                        };
+                        // Shift only lasts for one letter
+                        self.relabel(self.layer, false);
+                        Key::Char(c)
+                    }
+                    Action::Space => Key::Char(' '),
+                    Action::Backspace => Key::Backspace,
+                    Action::Enter => Key::Enter,
+                    Action::Shift => {
+                        self.relabel(self.layer, !self.shift);
+                        return None;
+                    }
+                    Action::Layer => {
+                        let layer = match self.layer {
+                            Layer::Letters => Layer::Numbers,
+                            Layer::Numbers | Layer::Symbols => Layer::Letters,
+                        };
+                        self.relabel(layer, false);
+                        return None;
+                    }
+                    Action::Symbols => {
+                        let layer = match self.layer {
+                            Layer::Numbers => Layer::Symbols,
+                            Layer::Letters | Layer::Symbols => Layer::Numbers,
+                        };
+                        self.relabel(layer, false);
+                        return None;
+                    }
+                };
+                if let Some(f) = self.on_key.as_mut() {
+                    f(key);
+                }
+                Some(WidgetEvent::Key(key))
+            }
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,99 @@
+//! Static text
+
+use alloc::string::String;
+
+use embedded_graphics::{pixelcolor::Rgb565, prelude::*, primitives::Rectangle, text::Alignment};
+
+use crate::font::{Font, TextBox};
+
+use super::{Canvas, DrawError, Widget, WidgetBase, theme};
+
+/// Text in a box, one line unless wrapping is on
+///
+/// Text that doesn't fit ends in "...".
+pub struct Label {
+    base: WidgetBase,
+    text: String,
+    alignment: Alignment,
+    /// Theme text color if None
+    color: Option<Rgb565>,
+    /// Theme font if None
+    font: Option<Font>,
+    wrap: bool,
+}
+
+impl Label {
+    /// Create a left-aligned label
+    pub fn new(bounds: Rectangle, text: &str) -> Self {
+        Self {
+            base: WidgetBase::new(bounds),
+            text: String::from(text),
+            alignment: Alignment::Left,
+            color: None,
+            font: None,
+            wrap: false,
+        }
+    }
+
+    /// Set the horizontal alignment within the bounds
+    pub fn with_alignment(mut self, alignment: Alignment) -> Self {
+        self.alignment = alignment;
+        self
+    }
+
+    /// Set the text color instead of the theme's
+    pub fn with_color(mut self, color: Rgb565) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    /// Set the font instead of the theme's, e.g. [`Theme::large_font`]
+    ///
+    /// [`Theme::large_font`]: super::Theme::large_font
+    pub fn with_font(mut self, font: Font) -> Self {
+        self.font = Some(font);
+        self
+    }
+
+    /// Break the text into as many lines as fit the height
+    pub fn with_wrap(mut self, wrap: bool) -> Self {
+        self.wrap = wrap;
+        self
+    }
+
+    /// Get the text
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Change the text, redrawing only if it differs
+    pub fn set_text(&mut self, text: &str) {
+        if self.text != text {
+            self.text.clear();
+            self.text.push_str(text);
+            self.base.invalidate();
+        }
+    }
+}
+
+impl Widget for Label {
+    fn base(&self) -> &WidgetBase {
+        &self.base
+    }
+
+    fn base_mut(&mut self) -> &mut WidgetBase {
+        &mut self.base
+    }
+
+    fn draw(&mut self, canvas: &mut Canvas<'_>) -> Result<(), DrawError> {
+        let theme = theme();
+        let bounds = self.base.bounds();
+        canvas.fill_solid(&bounds, theme.background)?;
+        let color = theme.active(&self.base, self.color.unwrap_or(theme.text));
+        let font = self.font.unwrap_or(theme.font);
+        TextBox::new(&self.text, bounds, font, color)
+            .with_alignment(self.alignment)
+            .with_wrap(self.wrap)
+            .draw(canvas)
+    }
+}
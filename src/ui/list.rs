@@ -0,0 +1,535 @@
+//! Vertically scrolling list with fling and overscroll bounce
+
+use alloc::{boxed::Box, string::String, vec::Vec};
+
+use embedded_graphics::{prelude::*, primitives::Rectangle, text::Alignment};
+
+use crate::axs5106l::{Motion, TouchEvent};
+
+use super::{
+    Canvas, DrawError, Theme, Widget, WidgetBase, WidgetEvent, draw_text, theme, with_canvas,
+};
+
+/// Finger travel before a touch becomes a drag instead of a tap (px)
+const TAP_SLOP: i32 = 8;
+/// Fraction of the velocity lost per second while coasting
+const FRICTION: f32 = 2.5;
+/// Flings are capped at this speed (px/s)
+const MAX_FLING: f32 = 4_000.0;
+/// Coasting stops below this speed (px/s)
+const MIN_SPEED: f32 = 20.0;
+/// Stiffness of the spring pulling an overscrolled list back (1/s²)
+const SPRING: f32 = 180.0;
+/// Damping of that spring, critical for `SPRING` (1/s)
+const DAMPING: f32 = 27.0;
+/// Longest physics step, longer frames are split (s)
+const MAX_STEP: f32 = 0.01;
+/// Width of the scrollbar
+const SCROLLBAR: u32 = 3;
+/// Shortest scrollbar thumb
+const MIN_THUMB: u32 = 12;
+/// Width of the mark left of the selected row, the text follows after the
+/// theme spacing
+const SELECTION_MARK: u32 = 3;
+
+struct Row {
+    text: String,
+    dirty: bool,
+}
+
+/// Finger holding the list
+struct Drag {
+    start_y: i32,
+    start_offset: f32,
+    /// Moved further than `TAP_SLOP`
+    moved: bool,
+}
+
+/// List of text rows scrolled by dragging and flinging
+///
+/// Tapping a row selects it. The list keeps coasting after a fling with the
+/// release velocity passed to [`Widget::fling`], and springs back when
+/// dragged or flung past either end. Only the rows that moved or changed
+/// are redrawn.
+pub struct ScrollList {
+    base: WidgetBase,
+    rows: Vec<Row>,
+    row_height: u32,
+    /// Distance scrolled from the top, negative or past the end while
+    /// overscrolled (px)
+    offset: f32,
+    velocity: f32,
+    drag: Option<Drag>,
+    /// Released after a drag, so a fling may follow
+    flingable: bool,
+    pressed: Option<usize>,
+    selected: Option<usize>,
+    last_ms: Option<u64>,
+    /// Offset of the last draw, None after an invalidate
+    drawn_offset: Option<i32>,
+    drawn_enabled: bool,
+    scrollbar_dirty: bool,
+    on_select: Option<Box<dyn FnMut(usize)>>,
+}
+
+impl ScrollList {
+    /// Create an empty list with rows of `row_height` pixels
+    pub fn new(bounds: Rectangle, row_height: u32) -> Self {
+        Self {
+            base: WidgetBase::new(bounds),
+            rows: Vec::new(),
+            row_height: row_height.max(1),
+            offset: 0.0,
+            velocity: 0.0,
+            drag: None,
+            flingable: false,
+            pressed: None,
+            selected: None,
+            last_ms: None,
+            drawn_offset: None,
+            drawn_enabled: true,
+            scrollbar_dirty: true,
+            on_select: None,
+        }
+    }
+
+    /// Fill the list with `items`
+    pub fn with_items<'a>(mut self, items: impl IntoIterator<Item = &'a str>) -> Self {
+        items.into_iter().for_each(|item| self.push(item));
+        self
+    }
+
+    /// Call `f` with the row index whenever the user taps a row
+    pub fn on_select(mut self, f: impl FnMut(usize) + 'static) -> Self {
+        self.on_select = Some(Box::new(f));
+        self
+    }
+
+    /// Number of rows
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Whether the list has no rows
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    /// Text of a row
+    pub fn item(&self, index: usize) -> Option<&str> {
+        self.rows.get(index).map(|r| r.text.as_str())
+    }
+
+    /// Append a row
+    pub fn push(&mut self, text: &str) {
+        self.rows.push(Row {
+            text: String::from(text),
+            dirty: true,
+        });
+        self.scrollbar_dirty = true;
+        self.base.invalidate();
+    }
+
+    /// Remove a row, the rows below move up
+    pub fn remove(&mut self, index: usize) {
+        if index >= self.rows.len() {
+            return;
+        }
+        self.rows.remove(index);
+        self.selected = match self.selected {
+            Some(i) if i == index => None,
+            Some(i) if i > index => Some(i - 1),
+            selected => selected,
+        };
+        self.pressed = None;
+        Widget::invalidate(self);
+    }
+
+    /// Change the text of a row, redrawing only that row
+    pub fn set_item(&mut self, index: usize, text: &str) {
+        if let Some(row) = self.rows.get_mut(index)
+            && row.text != text
+        {
+            row.text.clear();
+            row.text.push_str(text);
+            self.mark_row(index);
+        }
+    }
+
+    /// Remove all rows and scroll back to the top
+    pub fn clear(&mut self) {
+        self.rows.clear();
+        self.offset = 0.0;
+        self.velocity = 0.0;
+        self.drag = None;
+        self.pressed = None;
+        self.selected = None;
+        Widget::invalidate(self);
+    }
+
+    /// Index of the selected row
+    pub fn selected(&self) -> Option<usize> {
+        self.selected
+    }
+
+    /// Select a row without calling the callback
+    pub fn set_selected(&mut self, index: Option<usize>) {
+        let index = index.filter(|&i| i < self.rows.len());
+        if self.selected != index {
+            for i in [self.selected, index].into_iter().flatten() {
+                self.mark_row(i);
+            }
+            self.selected = index;
+        }
+    }
+
+    /// Scroll so row `index` is at the top, or as far as the list goes
+    pub fn scroll_to(&mut self, index: usize) {
+        self.velocity = 0.0;
+        self.drag = None;
+        let offset = (index as u32 * self.row_height) as f32;
+        self.set_offset(offset.min(self.max_offset()));
+    }
+
+    /// Distance scrolled from the top (px)
+    pub fn scroll_offset(&self) -> i32 {
+        self.offset as i32
+    }
+
+    /// Whether the list is moving on its own after a fling or overscroll
+    pub fn is_scrolling(&self) -> bool {
+        self.drag.is_none() && (self.velocity != 0.0 || self.overscroll() != 0.0)
+    }
+
+    fn content_height(&self) -> u32 {
+        self.rows.len() as u32 * self.row_height
+    }
+
+    fn max_offset(&self) -> f32 {
+        self.content_height()
+            .saturating_sub(self.base.bounds().size.height) as f32
+    }
+
+    /// Distance past the nearest end, negative above the top (px)
+    fn overscroll(&self) -> f32 {
+        if self.offset < 0.0 {
+            self.offset
+        } else {
+            (self.offset - self.max_offset()).max(0.0)
+        }
+    }
+
+    /// How far the list can be pulled past an end
+    fn max_overscroll(&self) -> f32 {
+        (self.base.bounds().size.height / 4) as f32
+    }
+
+    fn set_offset(&mut self, offset: f32) {
+        let limit = self.max_overscroll();
+        let offset = offset.clamp(-limit, self.max_offset() + limit);
+        if offset as i32 != self.offset as i32 {
+            self.base.invalidate();
+        }
+        self.offset = offset;
+    }
+
+    fn mark_row(&mut self, index: usize) {
+        if let Some(row) = self.rows.get_mut(index) {
+            row.dirty = true;
+            self.base.invalidate();
+        }
+    }
+
+    fn row_at(&self, y: i32) -> Option<usize> {
+        let top = self.base.bounds().top_left.y;
+        let content_y = y - top + self.offset as i32;
+        if content_y < 0 {
+            return None;
+        }
+        let index = content_y as usize / self.row_height as usize;
+        (index < self.rows.len()).then_some(index)
+    }
+
+    /// Area of the rows, left of the scrollbar
+    fn rows_area(&self) -> Rectangle {
+        let bounds = self.base.bounds();
+        Rectangle::new(
+            bounds.top_left,
+            Size::new(
+                bounds.size.width.saturating_sub(SCROLLBAR),
+                bounds.size.height,
+            ),
+        )
+    }
+
+    /// Rows visible at scroll offset `offset`
+    fn visible_rows(&self, offset: i32) -> core::ops::Range<usize> {
+        let height = self.row_height as i32;
+        let first = offset.max(0) / height;
+        let last = (offset + self.base.bounds().size.height as i32 + height - 1) / height;
+        (first as usize).min(self.rows.len())..(last.max(0) as usize).min(self.rows.len())
+    }
+
+    fn draw_row(
+        &self,
+        canvas: &mut Canvas<'_>,
+        theme: &Theme,
+        index: usize,
+        offset: i32,
+    ) -> Result<(), DrawError> {
+        let area = self.rows_area();
+        let y = area.top_left.y + (index as u32 * self.row_height) as i32 - offset;
+        let row = Rectangle::new(
+            Point::new(area.top_left.x, y),
+            Size::new(area.size.width, self.row_height),
+        );
+        let fill = if self.pressed == Some(index) {
+            theme.pressed
+        } else {
+            theme.background
+        };
+        canvas.fill_solid(&row, fill)?;
+
+        if self.selected == Some(index) {
+            let mark = Rectangle::new(row.top_left, Size::new(SELECTION_MARK, self.row_height));
+            canvas.fill_solid(&mark, theme.active(&self.base, theme.accent))?;
+        }
+
+        // Separator along the bottom edge
+        let separator = Rectangle::new(
+            Point::new(row.top_left.x, y + self.row_height as i32 - 1),
+            Size::new(row.size.width, 1),
+        );
+        canvas.fill_solid(&separator, theme.surface)?;
+
+        let text = Rectangle::new(
+            row.top_left + Point::new((SELECTION_MARK + theme.spacing) as i32, 0),
+            Size::new(
+                row.size
+                    .width
+                    .saturating_sub(SELECTION_MARK + theme.spacing),
+                self.row_height - 1,
+            ),
+        );
+        let color = theme.active(&self.base, theme.text);
+        draw_text(
+            canvas,
+            &self.rows[index].text,
+            &text,
+            Alignment::Left,
+            theme.font,
+            color,
+        )
+    }
+
+    /// Paint the empty space above the first and below the last row
+    fn draw_gaps(
+        &self,
+        canvas: &mut Canvas<'_>,
+        theme: &Theme,
+        offset: i32,
+    ) -> Result<(), DrawError> {
+        let area = self.rows_area();
+        if offset < 0 {
+            let top = Rectangle::new(area.top_left, Size::new(area.size.width, (-offset) as u32));
+            canvas.fill_solid(&top, theme.background)?;
+        }
+        let end = self.content_height() as i32 - offset;
+        if end < area.size.height as i32 {
+            let bottom = Rectangle::new(
+                area.top_left + Point::new(0, end.max(0)),
+                Size::new(area.size.width, area.size.height - end.max(0) as u32),
+            );
+            canvas.fill_solid(&bottom, theme.background)?;
+        }
+        Ok(())
+    }
+
+    fn draw_scrollbar(&self, canvas: &mut Canvas<'_>, theme: &Theme) -> Result<(), DrawError> {
+        let bounds = self.base.bounds();
+        let height = bounds.size.height;
+        let track = Rectangle::new(
+            bounds.top_left + Point::new(bounds.size.width.saturating_sub(SCROLLBAR) as i32, 0),
+            Size::new(SCROLLBAR.min(bounds.size.width), height),
+        );
+        canvas.fill_solid(&track, theme.background)?;
+
+        let content = self.content_height();
+        if content <= height {
+            return Ok(());
+        }
+        // Overscrolling shortens the thumb against the end it passed
+        let overscroll = self.overscroll().abs() as u32;
+        let thumb = (height * height / content)
+            .saturating_sub(overscroll)
+            .max(MIN_THUMB)
+            .min(height);
+        let progress = self.offset.clamp(0.0, self.max_offset()) / self.max_offset();
+        let y = ((height - thumb) as f32 * progress) as i32;
+        let thumb = Rectangle::new(
+            track.top_left + Point::new(0, y),
+            Size::new(track.size.width, thumb),
+        );
+        canvas.fill_solid(&thumb, theme.active(&self.base, theme.accent))
+    }
+
+    /// Advance the fling and spring-back by `dt` seconds
+    fn step(&mut self, dt: f32) {
+        let overscroll = self.overscroll();
+        if overscroll != 0.0 {
+            // Damped spring towards the end that was passed
+            self.velocity += (-SPRING * overscroll - DAMPING * self.velocity) * dt;
+        } else {
+            self.velocity -= self.velocity * (FRICTION * dt).min(1.0);
+        }
+        self.set_offset(self.offset + self.velocity * dt);
+
+        // Stop at the overscroll limit instead of pushing against it
+        let overscroll = self.overscroll();
+        if overscroll.abs() >= self.max_overscroll() && overscroll * self.velocity > 0.0 {
+            self.velocity = 0.0;
+        }
+
+        if self.velocity.abs() < MIN_SPEED {
+            if overscroll == 0.0 {
+                self.velocity = 0.0;
+            } else if overscroll.abs() < 0.5 {
+                self.velocity = 0.0;
+                self.set_offset(self.offset - overscroll);
+            }
+        }
+    }
+}
+
+impl Widget for ScrollList {
+    fn base(&self) -> &WidgetBase {
+        &self.base
+    }
+
+    fn base_mut(&mut self) -> &mut WidgetBase {
+        &mut self.base
+    }
+
+    fn draw(&mut self, canvas: &mut Canvas<'_>) -> Result<(), DrawError> {
+        let theme = theme();
+        let offset = self.offset as i32;
+        // Enabling or disabling changes the colors of every row
+        let moved =
+            self.drawn_offset != Some(offset) || self.drawn_enabled != self.base.is_enabled();
+        let bounds = self.base.bounds();
+
+        // Rows sticking out of the list are cut at its edges
+        let mut clipped = canvas.clipped(&bounds);
+        with_canvas(&mut clipped, |canvas| {
+            for index in self.visible_rows(offset) {
+                if moved || self.rows[index].dirty {
+                    self.draw_row(canvas, &theme, index, offset)?;
+                }
+            }
+            if moved {
+                self.draw_gaps(canvas, &theme, offset)?;
+            }
+            if moved || self.scrollbar_dirty {
+                self.draw_scrollbar(canvas, &theme)?;
+            }
+            Ok(())
+        })?;
+
+        self.rows.iter_mut().for_each(|r| r.dirty = false);
+        self.drawn_offset = Some(offset);
+        self.drawn_enabled = self.base.is_enabled();
+        self.scrollbar_dirty = false;
+        Ok(())
+    }
+
+    fn invalidate(&mut self) {
+        self.drawn_offset = None;
+        self.base.invalidate();
+    }
+
+    fn touch(&mut self, event: &TouchEvent) -> Option<WidgetEvent> {
+        let (_, y) = event.position()?;
+        let y = y as i32;
+
+        match event {
+            TouchEvent::Down { .. } => {
+                // Catching a coasting list stops it without selecting a row
+                let coasting = self.velocity.abs() >= MIN_SPEED;
+                self.velocity = 0.0;
+                self.flingable = false;
+                self.drag = Some(Drag {
+                    start_y: y,
+                    start_offset: self.offset,
+                    moved: coasting,
+                });
+                if !coasting {
+                    self.pressed = self.row_at(y);
+                    if let Some(i) = self.pressed {
+                        self.mark_row(i);
+                    }
+                }
+                None
+            }
+            TouchEvent::Move { .. } => {
+                let drag = self.drag.as_mut()?;
+                let distance = drag.start_y - y;
+                if !drag.moved && distance.abs() <= TAP_SLOP {
+                    return None;
+                }
+                drag.moved = true;
+                let raw = drag.start_offset + distance as f32;
+                if let Some(i) = self.pressed.take() {
+                    self.mark_row(i);
+                }
+
+                // Past either end the list follows the finger at half speed
+                let max = self.max_offset();
+                let offset = if raw < 0.0 {
+                    raw / 2.0
+                } else if raw > max {
+                    max + (raw - max) / 2.0
+                } else {
+                    raw
+                };
+                self.set_offset(offset);
+                None
+            }
+            TouchEvent::Up { .. } => {
+                let drag = self.drag.take()?;
+                let pressed = self.pressed.take();
+                if let Some(i) = pressed {
+                    self.mark_row(i);
+                }
+                self.flingable = drag.moved;
+
+                let index = pressed.filter(|&i| !drag.moved && self.row_at(y) == Some(i))?;
+                self.set_selected(Some(index));
+                if let Some(f) = self.on_select.as_mut() {
+                    f(index);
+                }
+                Some(WidgetEvent::Selected(index))
+            }
+            _ => None,
+        }
+    }
+
+    fn fling(&mut self, motion: &Motion) {
+        if core::mem::take(&mut self.flingable) {
+            // Content moves with the finger, the offset against it
+            self.velocity = (-motion.vy).clamp(-MAX_FLING, MAX_FLING);
+        }
+    }
+
+    fn update(&mut self, now_ms: u64) {
+        let last_ms = self.last_ms.replace(now_ms).unwrap_or(now_ms);
+        if !self.is_scrolling() {
+            return;
+        }
+        let mut dt = now_ms.saturating_sub(last_ms) as f32 / 1_000.0;
+        while dt > 0.0 {
+            let step = dt.min(MAX_STEP);
+            self.step(step);
+            dt -= step;
+        }
+    }
+}
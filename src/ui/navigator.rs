@@ -0,0 +1,399 @@
+//! Screens and navigation between them
+//!
+//! A [`Navigator`] holds a row of pages that the user swipes through
+//! horizontally, and a stack of screens pushed on top of them (e.g. a
+//! settings dialog). Page changes, pushes and pops are shown as a wipe:
+//! an edge moves across the display over [`Navigator::with_transition_ms`]
+//! and uncovers the new screen in place, neither screen is translated.
+//! A swipe in from the left edge pops the top screen.
+//!
+//! Every frame of a wipe repaints the new screen through a clip to the
+//! strip uncovered since the last frame, and draws its changes in the
+//! part already uncovered. Only those pixels reach the target, but the
+//! screen's widgets are all drawn each frame.
+
+use alloc::{boxed::Box, vec::Vec};
+use core::any::Any;
+
+use embedded_graphics::{pixelcolor::Rgb565, prelude::*, primitives::Rectangle};
+
+use crate::axs5106l::{Motion, TouchEvent};
+use crate::gesture::{Direction, Edge, Gesture, GestureConfig, GestureDetector};
+
+use super::{Canvas, DrawError, Ui, with_canvas};
+
+/// Default duration of a wipe transition (ms)
+const TRANSITION_MS: u64 = 250;
+
+/// Navigation requested by a screen
+pub enum Navigation {
+    /// Show a new screen on top of the current one
+    Push(Box<dyn Screen>),
+    /// Close the current screen and return to the one below
+    Pop,
+    /// Wipe to the next page
+    NextPage,
+    /// Wipe to the previous page
+    PreviousPage,
+}
+
+/// One full-screen view
+pub trait Screen: Any {
+    /// The screen became visible
+    fn on_enter(&mut self) {}
+
+    /// The screen is no longer visible
+    fn on_leave(&mut self) {}
+
+    /// Handle a touch event
+    fn on_touch(&mut self, _event: &TouchEvent) -> Option<Navigation> {
+        None
+    }
+
+    /// Release velocity of finger `id`, right after its `Up` event
+    fn on_fling(&mut self, _id: u8, _motion: &Motion) {}
+
+    /// Advance timers and animations
+    fn update(&mut self, _now_ms: u64) -> Option<Navigation> {
+        None
+    }
+
+    /// Draw what changed since the last call
+    fn draw(&mut self, canvas: &mut Canvas<'_>) -> Result<(), DrawError>;
+
+    /// Repaint the whole screen, background included, on the next draw
+    fn invalidate(&mut self);
+}
+
+/// A plain widget set as a screen; needs a background to cover the screen
+impl Screen for Ui {
+    fn on_touch(&mut self, event: &TouchEvent) -> Option<Navigation> {
+        self.handle_event(event);
+        None
+    }
+
+    fn on_fling(&mut self, id: u8, motion: &Motion) {
+        self.fling(id, motion);
+    }
+
+    fn update(&mut self, now_ms: u64) -> Option<Navigation> {
+        Ui::update(self, now_ms);
+        None
+    }
+
+    fn draw(&mut self, canvas: &mut Canvas<'_>) -> Result<(), DrawError> {
+        self.paint(canvas)
+    }
+
+    fn invalidate(&mut self) {
+        Ui::invalidate(self);
+    }
+}
+
+/// Position of a screen in the navigator
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Slot {
+    Page(usize),
+    Stack(usize),
+}
+
+/// Wipe from one screen to another
+struct Transition {
+    /// 1 if the new screen comes in from the right, -1 from the left
+    direction: i32,
+    start_ms: u64,
+    /// Width of the new screen drawn so far (pixels)
+    revealed: i32,
+}
+
+/// Page row plus screen stack with wipe transitions
+pub struct Navigator {
+    size: Size,
+    pages: Vec<Box<dyn Screen>>,
+    page: usize,
+    stack: Vec<Box<dyn Screen>>,
+    gestures: GestureDetector,
+    transition: Option<Transition>,
+    transition_ms: u64,
+    now_ms: u64,
+}
+
+impl Navigator {
+    /// Create a navigator for a display of `width` x `height` pixels
+    pub fn new(width: u16, height: u16) -> Self {
+        Self {
+            size: Size::new(width as u32, height as u32),
+            pages: Vec::new(),
+            page: 0,
+            stack: Vec::new(),
+            gestures: GestureDetector::new(GestureConfig::new(width, height)),
+            transition: None,
+            transition_ms: TRANSITION_MS,
+            now_ms: 0,
+        }
+    }
+
+    /// Set the duration of wipe transitions, 0 switches instantly
+    pub fn with_transition_ms(mut self, transition_ms: u64) -> Self {
+        self.transition_ms = transition_ms;
+        self
+    }
+
+    /// Add a page to the right of the existing ones
+    pub fn add_page(&mut self, screen: impl Screen + 'static) {
+        let mut screen: Box<dyn Screen> = Box::new(screen);
+        if self.pages.is_empty() && self.stack.is_empty() {
+            screen.on_enter();
+        }
+        self.pages.push(screen);
+    }
+
+    /// Get a page by index, e.g. to update what it shows
+    pub fn page_mut<S: Screen>(&mut self, index: usize) -> Option<&mut S> {
+        let screen: &mut dyn Any = self.pages.get_mut(index)?.as_mut();
+        screen.downcast_mut()
+    }
+
+    /// Index of the visible page
+    pub fn page(&self) -> usize {
+        self.page
+    }
+
+    /// Number of screens pushed on top of the pages
+    pub fn depth(&self) -> usize {
+        self.stack.len()
+    }
+
+    /// Whether a wipe transition is running
+    pub fn is_animating(&self) -> bool {
+        self.transition.is_some()
+    }
+
+    /// Show `screen` on top of the current one
+    pub fn push(&mut self, screen: Box<dyn Screen>) {
+        let from = self.current();
+        self.stack.push(screen);
+        self.start(Some(from), 1);
+    }
+
+    /// Close the top screen, returns false if there is none
+    pub fn pop(&mut self) -> bool {
+        let Some(mut screen) = self.stack.pop() else {
+            return false;
+        };
+        screen.on_leave();
+        self.start(None, -1);
+        true
+    }
+
+    /// Wipe to page `index`, returns false if it doesn't exist or a screen
+    /// is pushed on top of the pages
+    pub fn show_page(&mut self, index: usize) -> bool {
+        if index >= self.pages.len() || index == self.page || !self.stack.is_empty() {
+            return false;
+        }
+        let from = self.current();
+        let direction = if index > self.page { 1 } else { -1 };
+        self.page = index;
+        self.start(Some(from), direction);
+        true
+    }
+
+    /// Route a touch event to the visible screen and detect page swipes
+    pub fn handle_event(&mut self, event: &TouchEvent) {
+        // Touches during a wipe would land on a half drawn screen
+        if self.transition.is_some() {
+            self.gestures.reset();
+            return;
+        }
+
+        let current = self.current();
+        let navigation = self.screen_mut(current).and_then(|s| s.on_touch(event));
+        if let Some(navigation) = navigation {
+            self.navigate(navigation);
+            self.gestures.reset();
+            return;
+        }
+
+        match self.gestures.update(event) {
+            Some(Gesture::EdgeSwipe { edge: Edge::Left }) if !self.stack.is_empty() => {
+                self.pop();
+            }
+            Some(
+                Gesture::Swipe {
+                    direction: Direction::Left,
+                }
+                | Gesture::EdgeSwipe { edge: Edge::Right },
+            ) => {
+                self.show_page(self.page + 1);
+            }
+            Some(
+                Gesture::Swipe {
+                    direction: Direction::Right,
+                }
+                | Gesture::EdgeSwipe { edge: Edge::Left },
+            ) => {
+                self.show_page(self.page.wrapping_sub(1));
+            }
+            _ => {}
+        }
+    }
+
+    /// Pass the release velocity of finger `id` to the visible screen
+    ///
+    /// Call it after [`handle_event`](Self::handle_event) with the `Up`
+    /// event, with the estimate from
+    /// [`Axs5106l::motion`](crate::axs5106l::Axs5106l::motion).
+    pub fn fling(&mut self, id: u8, motion: &Motion) {
+        if self.transition.is_some() {
+            return;
+        }
+        let current = self.current();
+        if let Some(screen) = self.screen_mut(current) {
+            screen.on_fling(id, motion);
+        }
+    }
+
+    /// Advance the visible screen and the transition
+    ///
+    /// The transition ends with the draw that uncovers the last strip.
+    pub fn update(&mut self, now_ms: u64) {
+        self.now_ms = now_ms;
+
+        let current = self.current();
+        let navigation = self.screen_mut(current).and_then(|s| s.update(now_ms));
+        if let Some(navigation) = navigation {
+            self.navigate(navigation);
+        }
+    }
+
+    /// Draw the visible screen, or the newly uncovered strip while wiping
+    pub fn draw<D>(&mut self, target: &mut D) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = Rgb565>,
+    {
+        with_canvas(target, |canvas| {
+            let current = self.current();
+            let Some(t) = &self.transition else {
+                return match self.screen_mut(current) {
+                    Some(screen) => screen.draw(canvas),
+                    None => Ok(()),
+                };
+            };
+
+            let (direction, revealed) = (t.direction, t.revealed);
+            let offset = self.offset(t.start_ms);
+            let width = self.size.width as i32;
+            let height = self.size.height;
+
+            // Part of the screen between x0 and x1, whichever side it is on
+            let part = |x0: i32, x1: i32| {
+                let left = if direction > 0 { width - x1 } else { x0 };
+                Rectangle::new(Point::new(left, 0), Size::new((x1 - x0) as u32, height))
+            };
+            let Some(screen) = self.screen_mut(current) else {
+                return Ok(());
+            };
+
+            // Changes in the part that is already visible
+            if revealed > 0 {
+                let mut clipped = canvas.clipped(&part(0, revealed));
+                with_canvas(&mut clipped, |c| screen.draw(c))?;
+            }
+            // Repaint the whole screen, clipped to the strip uncovered since the last frame
+            if offset > revealed {
+                screen.invalidate();
+                let mut clipped = canvas.clipped(&part(revealed, offset));
+                with_canvas(&mut clipped, |c| screen.draw(c))?;
+            }
+
+            if offset >= width {
+                self.transition = None;
+            } else if let Some(t) = self.transition.as_mut() {
+                t.revealed = offset;
+            }
+            Ok(())
+        })
+    }
+
+    /// Repaint every screen when it is drawn next, e.g. after a theme change
+    pub fn invalidate(&mut self) {
+        self.pages
+            .iter_mut()
+            .chain(self.stack.iter_mut())
+            .for_each(|s| s.invalidate());
+    }
+
+    fn navigate(&mut self, navigation: Navigation) {
+        match navigation {
+            Navigation::Push(screen) => self.push(screen),
+            Navigation::Pop => {
+                self.pop();
+            }
+            Navigation::NextPage => {
+                self.show_page(self.page + 1);
+            }
+            Navigation::PreviousPage => {
+                self.show_page(self.page.wrapping_sub(1));
+            }
+        }
+    }
+
+    fn current(&self) -> Slot {
+        match self.stack.len() {
+            0 => Slot::Page(self.page),
+            n => Slot::Stack(n - 1),
+        }
+    }
+
+    fn screen_mut(&mut self, slot: Slot) -> Option<&mut Box<dyn Screen>> {
+        match slot {
+            Slot::Page(i) => self.pages.get_mut(i),
+            Slot::Stack(i) => self.stack.get_mut(i),
+        }
+    }
+
+    /// Start wiping from `from` (None if it was popped) to the current screen
+    fn start(&mut self, from: Option<Slot>, direction: i32) {
+        self.finish();
+        self.gestures.reset();
+        if let Some(screen) = from.and_then(|from| self.screen_mut(from)) {
+            screen.on_leave();
+        }
+        let current = self.current();
+        if let Some(screen) = self.screen_mut(current) {
+            screen.on_enter();
+            screen.invalidate();
+        }
+
+        if self.transition_ms == 0 {
+            return;
+        }
+        self.transition = Some(Transition {
+            direction,
+            start_ms: self.now_ms,
+            revealed: 0,
+        });
+    }
+
+    /// End the running transition, leaving the current screen fully drawn
+    fn finish(&mut self) {
+        if self.transition.take().is_some() {
+            // Cut short, so part of the screen is still covered
+            let current = self.current();
+            if let Some(screen) = self.screen_mut(current) {
+                screen.invalidate();
+            }
+        }
+    }
+
+    /// Distance travelled so far, eased out (pixels)
+    fn offset(&self, start_ms: u64) -> i32 {
+        let width = self.size.width as i64;
+        let elapsed = self.now_ms.saturating_sub(start_ms).min(self.transition_ms) as i64;
+        let remaining = 1_000 - elapsed * 1_000 / self.transition_ms.max(1) as i64;
+        // 1 - (1 - t)^3 in thousandths
+        let eased = 1_000 - remaining * remaining * remaining / 1_000_000;
+        (width * eased / 1_000) as i32
+    }
+}
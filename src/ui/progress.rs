@@ -0,0 +1,61 @@
+//! Progress bar
+
+use embedded_graphics::{prelude::*, primitives::Rectangle};
+
+use super::{Canvas, DrawError, Widget, WidgetBase, theme};
+
+/// Horizontal bar filled from the left
+pub struct ProgressBar {
+    base: WidgetBase,
+    value: u32,
+    max: u32,
+}
+
+impl ProgressBar {
+    /// Create an empty bar for values in `0..=max`
+    pub fn new(bounds: Rectangle, max: u32) -> Self {
+        Self {
+            base: WidgetBase::new(bounds),
+            value: 0,
+            max: max.max(1),
+        }
+    }
+
+    /// Get the value
+    pub fn value(&self) -> u32 {
+        self.value
+    }
+
+    /// Set the value, clamped to `max`
+    pub fn set_value(&mut self, value: u32) {
+        let value = value.min(self.max);
+        if self.value != value {
+            self.value = value;
+            self.base.invalidate();
+        }
+    }
+}
+
+impl Widget for ProgressBar {
+    fn base(&self) -> &WidgetBase {
+        &self.base
+    }
+
+    fn base_mut(&mut self) -> &mut WidgetBase {
+        &mut self.base
+    }
+
+    fn draw(&mut self, canvas: &mut Canvas<'_>) -> Result<(), DrawError> {
+        let theme = theme();
+        let bounds = self.base.bounds();
+        let width = (bounds.size.width as u64 * self.value as u64 / self.max as u64) as u32;
+
+        let filled = Rectangle::new(bounds.top_left, Size::new(width, bounds.size.height));
+        canvas.fill_solid(&filled, theme.active(&self.base, theme.accent))?;
+        let empty = Rectangle::new(
+            bounds.top_left + Point::new(width as i32, 0),
+            Size::new(bounds.size.width - width, bounds.size.height),
+        );
+        canvas.fill_solid(&empty, theme.surface)
+    }
+}
@@ -0,0 +1,148 @@
+//! Horizontal slider
+
+use alloc::boxed::Box;
+
+use embedded_graphics::{
+    prelude::*,
+    primitives::{Circle, PrimitiveStyle, Rectangle},
+};
+
+use crate::axs5106l::TouchEvent;
+
+use super::{Canvas, DrawError, Widget, WidgetBase, WidgetEvent, theme};
+
+/// Height of the track in pixels
+const TRACK: u32 = 4;
+
+/// Slider picking an integer value by dragging the knob
+pub struct Slider {
+    base: WidgetBase,
+    min: i32,
+    max: i32,
+    value: i32,
+    on_change: Option<Box<dyn FnMut(i32)>>,
+}
+
+impl Slider {
+    /// Create a slider for values in `min..=max`
+    pub fn new(bounds: Rectangle, min: i32, max: i32, value: i32) -> Self {
+        let max = max.max(min);
+        Self {
+            base: WidgetBase::new(bounds),
+            min,
+            max,
+            value: value.clamp(min, max),
+            on_change: None,
+        }
+    }
+
+    /// Call `f` with the new value while the user drags the slider
+    pub fn on_change(mut self, f: impl FnMut(i32) + 'static) -> Self {
+        self.on_change = Some(Box::new(f));
+        self
+    }
+
+    /// Get the value
+    pub fn value(&self) -> i32 {
+        self.value
+    }
+
+    /// Set the value without calling the callback
+    pub fn set_value(&mut self, value: i32) {
+        let value = value.clamp(self.min, self.max);
+        if self.value != value {
+            self.value = value;
+            self.base.invalidate();
+        }
+    }
+
+    /// Diameter of the knob
+    fn knob(&self) -> u32 {
+        self.base.bounds().size.height
+    }
+
+    /// X range the knob center moves in
+    fn travel(&self) -> (i32, i32) {
+        let bounds = self.base.bounds();
+        let radius = (self.knob() / 2) as i32;
+        let start = bounds.top_left.x + radius;
+        let end = bounds.top_left.x + bounds.size.width as i32 - 1 - radius;
+        (start, end.max(start))
+    }
+
+    fn value_at(&self, x: i32) -> i32 {
+        let (start, end) = self.travel();
+        let span = (end - start).max(1);
+        let offset = (x - start).clamp(0, span);
+        self.min + ((self.max - self.min) * offset + span / 2) / span
+    }
+
+    fn position_of(&self, value: i32) -> i32 {
+        let (start, end) = self.travel();
+        let range = (self.max - self.min).max(1);
+        start + (end - start) * (value - self.min) / range
+    }
+}
+
+impl Widget for Slider {
+    fn base(&self) -> &WidgetBase {
+        &self.base
+    }
+
+    fn base_mut(&mut self) -> &mut WidgetBase {
+        &mut self.base
+    }
+
+    fn draw(&mut self, canvas: &mut Canvas<'_>) -> Result<(), DrawError> {
+        let theme = theme();
+        let bounds = self.base.bounds();
+        canvas.fill_solid(&bounds, theme.background)?;
+
+        let (start, end) = self.travel();
+        let knob_x = self.position_of(self.value);
+        let track_y = bounds.center().y - (TRACK / 2) as i32;
+
+        // Filled part left of the knob, empty part right of it
+        let filled = Rectangle::with_corners(
+            Point::new(start, track_y),
+            Point::new(knob_x, track_y + TRACK as i32 - 1),
+        );
+        canvas.fill_solid(&filled, theme.active(&self.base, theme.accent))?;
+        let empty = Rectangle::with_corners(
+            Point::new(knob_x, track_y),
+            Point::new(end, track_y + TRACK as i32 - 1),
+        );
+        canvas.fill_solid(&empty, theme.fill(&self.base))?;
+
+        let knob = if self.base.is_pressed() {
+            theme.accent
+        } else {
+            theme.active(&self.base, theme.text)
+        };
+        Circle::with_center(Point::new(knob_x, bounds.center().y), self.knob())
+            .into_styled(PrimitiveStyle::with_fill(knob))
+            .draw(canvas)?;
+        Ok(())
+    }
+
+    fn touch(&mut self, event: &TouchEvent) -> Option<WidgetEvent> {
+        let (x, _) = event.position()?;
+
+        if let TouchEvent::Up { .. } = event {
+            self.base.set_pressed(false);
+            return None;
+        }
+
+        // The knob follows the finger even outside the bounds
+        self.base.set_pressed(true);
+        let value = self.value_at(x as i32);
+        if value == self.value {
+            return None;
+        }
+        self.set_value(value);
+        if let Some(f) = self.on_change.as_mut() {
+            f(value);
+        }
+        Some(WidgetEvent::Changed(value))
+    }
+}
@@ -0,0 +1,246 @@
+//! Single-line text input
+
+use alloc::string::String;
+
+use embedded_graphics::{
+    prelude::*,
+    primitives::{PrimitiveStyle, Rectangle},
+    text::Alignment,
+};
+
+use crate::axs5106l::TouchEvent;
+
+use super::{Canvas, DrawError, Key, Theme, Widget, WidgetBase, WidgetEvent, draw_text, theme};
+
+/// Cursor on and off time (ms)
+const BLINK_MS: u64 = 500;
+/// Character shown instead of each character of a password
+const MASK: char = '*';
+
+/// Text field with a blinking cursor, edited with [`Key`]s from a
+/// [`Keyboard`](super::Keyboard)
+///
+/// Tapping the text moves the cursor. Text longer than the field scrolls
+/// so the cursor stays visible.
+pub struct TextField {
+    base: WidgetBase,
+    text: String,
+    placeholder: String,
+    password: bool,
+    max_len: usize,
+    /// Cursor position in characters
+    cursor: usize,
+    /// First visible character
+    scroll: usize,
+    cursor_visible: bool,
+    blink_ms: Option<u64>,
+}
+
+impl TextField {
+    /// Create an empty text field
+    pub fn new(bounds: Rectangle) -> Self {
+        Self {
+            base: WidgetBase::new(bounds),
+            text: String::new(),
+            placeholder: String::new(),
+            password: false,
+            max_len: usize::MAX,
+            cursor: 0,
+            scroll: 0,
+            cursor_visible: true,
+            blink_ms: None,
+        }
+    }
+
+    /// Show `text` in grey while the field is empty
+    pub fn with_placeholder(mut self, text: &str) -> Self {
+        self.placeholder = String::from(text);
+        self
+    }
+
+    /// Hide the text behind `*`, e.g. for Wi-Fi passwords
+    pub fn with_password(mut self, password: bool) -> Self {
+        self.password = password;
+        self
+    }
+
+    /// Limit the text to `max_len` characters
+    pub fn with_max_len(mut self, max_len: usize) -> Self {
+        self.max_len = max_len;
+        self
+    }
+
+    /// Get the text
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Replace the text, the cursor goes to the end
+    pub fn set_text(&mut self, text: &str) {
+        self.text.clear();
+        self.text.extend(text.chars().take(self.max_len));
+        self.move_cursor(self.len());
+        self.base.invalidate();
+    }
+
+    /// Cursor position in characters
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    /// Apply a key from the keyboard, returns true if the text changed
+    pub fn key(&mut self, key: Key) -> bool {
+        match key {
+            Key::Char(c) if self.len() < self.max_len => {
+                let at = self.byte_index(self.cursor);
+                self.text.insert(at, c);
+                self.move_cursor(self.cursor + 1);
+            }
+            Key::Backspace if self.cursor > 0 => {
+                let at = self.byte_index(self.cursor - 1);
+                self.text.remove(at);
+                self.move_cursor(self.cursor - 1);
+            }
+            _ => return false,
+        }
+        self.base.invalidate();
+        true
+    }
+
+    fn len(&self) -> usize {
+        self.text.chars().count()
+    }
+
+    fn byte_index(&self, chars: usize) -> usize {
+        self.text
+            .char_indices()
+            .nth(chars)
+            .map_or(self.text.len(), |(i, _)| i)
+    }
+
+    /// Characters as drawn, masked for passwords
+    fn shown(&self) -> impl Iterator<Item = char> + '_ {
+        let password = self.password;
+        self.text
+            .chars()
+            .map(move |c| if password { MASK } else { c })
+    }
+
+    /// Width of the characters from `from` up to `to`
+    fn width(&self, theme: &Theme, from: usize, to: usize) -> u32 {
+        let chars = self.shown().skip(from).take(to.saturating_sub(from));
+        chars.map(|c| theme.font.advance(c)).sum()
+    }
+
+    /// Area of the text inside the frame
+    fn inner(&self, theme: &Theme) -> Rectangle {
+        let bounds = self.base.bounds();
+        let padding = padding(theme);
+        Rectangle::new(
+            bounds.top_left + Point::new(padding, 0),
+            Size::new(
+                bounds.size.width.saturating_sub(2 * padding as u32),
+                bounds.size.height,
+            ),
+        )
+    }
+
+    /// Move the cursor, scrolling it into view and showing it at once
+    fn move_cursor(&mut self, cursor: usize) {
+        let theme = theme();
+        self.cursor = cursor.min(self.len());
+        // Keep a pixel right of the text for the cursor
+        let width = self.inner(&theme).size.width.saturating_sub(1);
+        self.scroll = self.scroll.min(self.cursor);
+        while self.scroll < self.cursor && self.width(&theme, self.scroll, self.cursor) > width {
+            self.scroll += 1;
+        }
+        self.cursor_visible = true;
+        self.blink_ms = None;
+        self.base.invalidate();
+    }
+}
+
+impl Widget for TextField {
+    fn base(&self) -> &WidgetBase {
+        &self.base
+    }
+
+    fn base_mut(&mut self) -> &mut WidgetBase {
+        &mut self.base
+    }
+
+    fn draw(&mut self, canvas: &mut Canvas<'_>) -> Result<(), DrawError> {
+        let theme = theme();
+        let bounds = self.base.bounds();
+        canvas.fill_solid(&bounds, theme.background)?;
+        bounds
+            .into_styled(PrimitiveStyle::with_stroke(
+                theme.active(&self.base, theme.surface),
+                1,
+            ))
+            .draw(canvas)?;
+
+        let inner = self.inner(&theme);
+        let top = inner.center().y - (theme.font.line_height() / 2) as i32;
+        if self.text.is_empty() {
+            let placeholder = &self.placeholder;
+            draw_text(
+                canvas,
+                placeholder,
+                &inner,
+                Alignment::Left,
+                theme.font,
+                theme.muted,
+            )?;
+        } else {
+            // Characters past the right edge are cut off, not ellipsized
+            let visible: String = self.shown().skip(self.scroll).collect();
+            let color = theme.active(&self.base, theme.text);
+            let position = Point::new(inner.top_left.x, top);
+            theme
+                .font
+                .draw(&mut canvas.clipped(&inner), &visible, position, color)?;
+        }
+
+        if self.cursor_visible && self.base.is_enabled() {
+            let x = inner.top_left.x + self.width(&theme, self.scroll, self.cursor) as i32 - 1;
+            let cursor = Rectangle::new(Point::new(x, top), Size::new(1, theme.font.line_height()));
+            canvas.fill_solid(&cursor, theme.accent)?;
+        }
+        Ok(())
+    }
+
+    fn touch(&mut self, event: &TouchEvent) -> Option<WidgetEvent> {
+        // Place the cursor at the character boundary closest to the finger
+        if let TouchEvent::Down { x, .. } | TouchEvent::Move { x, .. } = *event {
+            let theme = theme();
+            let mut left = self.inner(&theme).top_left.x;
+            let mut column = 0;
+            for c in self.shown().skip(self.scroll) {
+                let advance = theme.font.advance(c) as i32;
+                if (x as i32) < left + advance / 2 {
+                    break;
+                }
+                left += advance;
+                column += 1;
+            }
+            self.move_cursor(self.scroll + column);
+        }
+        None
+    }
+
+    fn update(&mut self, now_ms: u64) {
+        let start = *self.blink_ms.get_or_insert(now_ms);
+        let visible = (now_ms - start) / BLINK_MS % 2 == 0;
+        if visible != self.cursor_visible {
+            self.cursor_visible = visible;
+            self.base.invalidate();
+        }
+    }
+}
+
+/// Space between the frame and the text
+fn padding(theme: &Theme) -> i32 {
+    (theme.spacing / 2) as i32
+}
@@ -0,0 +1,112 @@
+//! Colors, fonts and sizes shared by all widgets
+
+use core::cell::Cell;
+
+use critical_section::Mutex;
+use embedded_graphics::{mono_font::ascii::FONT_6X10, pixelcolor::Rgb565, prelude::*};
+
+use crate::font::{FIXED_13, FIXED_15_BOLD, Font};
+
+use super::WidgetBase;
+
+/// Look of the widgets
+///
+/// Widgets read the current theme every time they draw, so after
+/// [`set_theme`] a full redraw (e.g. [`Navigator::invalidate`]) shows the
+/// new look everywhere.
+///
+/// [`Navigator::invalidate`]: super::Navigator::invalidate
+#[derive(Clone, Copy, Debug)]
+pub struct Theme {
+    /// Screen background behind widgets
+    pub background: Rgb565,
+    /// Fill of buttons, tracks, keys and boxes
+    pub surface: Rgb565,
+    /// Fill of a pressed widget
+    pub pressed: Rgb565,
+    /// Active parts: toggle on, slider fill, progress, check mark, chart line
+    pub accent: Rgb565,
+    /// Second brand color, for a second chart or highlights
+    pub secondary: Rgb565,
+    /// Text and knobs
+    pub text: Rgb565,
+    /// Placeholders, axis labels, and text and active parts of a disabled
+    /// widget
+    pub muted: Rgb565,
+    /// Text of buttons, labels, lists and the keyboard
+    pub font: Font,
+    /// Text of chart axes and other secondary text
+    pub small_font: Font,
+    /// Values meant to be read from a distance, e.g. sensor readings
+    pub large_font: Font,
+    /// Space between parts of a widget, e.g. a check box and its caption
+    pub spacing: u32,
+    /// Corner radius of buttons and keys
+    pub radius: u32,
+}
+
+impl Theme {
+    /// Light text on black, the default
+    pub const DARK: Theme = Theme {
+        background: Rgb565::BLACK,
+        surface: Rgb565::new(6, 12, 6),
+        pressed: Rgb565::new(12, 24, 12),
+        accent: Rgb565::new(0, 36, 31),
+        secondary: Rgb565::new(31, 40, 0),
+        text: Rgb565::WHITE,
+        muted: Rgb565::new(12, 24, 12),
+        font: Font::bitmap(&FIXED_13),
+        small_font: Font::mono(&FONT_6X10),
+        large_font: Font::bitmap(&FIXED_15_BOLD),
+        spacing: 6,
+        radius: 6,
+    };
+
+    /// Dark text on white, readable in sunlight
+    pub const LIGHT: Theme = Theme {
+        background: Rgb565::WHITE,
+        surface: Rgb565::new(26, 52, 26),
+        pressed: Rgb565::new(20, 40, 20),
+        accent: Rgb565::new(0, 24, 22),
+        secondary: Rgb565::new(26, 22, 0),
+        text: Rgb565::BLACK,
+        muted: Rgb565::new(16, 32, 16),
+        font: Font::bitmap(&FIXED_13),
+        small_font: Font::mono(&FONT_6X10),
+        large_font: Font::bitmap(&FIXED_15_BOLD),
+        spacing: 6,
+        radius: 6,
+    };
+
+    /// `color` while the widget is enabled, muted when disabled
+    pub fn active(&self, base: &WidgetBase, color: Rgb565) -> Rgb565 {
+        if base.is_enabled() { color } else { self.muted }
+    }
+
+    /// Fill of a widget body, lighter while pressed
+    pub fn fill(&self, base: &WidgetBase) -> Rgb565 {
+        if base.is_pressed() {
+            self.pressed
+        } else {
+            self.surface
+        }
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::DARK
+    }
+}
+
+static THEME: Mutex<Cell<Theme>> = Mutex::new(Cell::new(Theme::DARK));
+
+/// Get the current theme
+pub fn theme() -> Theme {
+    critical_section::with(|cs| THEME.borrow(cs).get())
+}
+
+/// Switch all widgets to `theme` from their next redraw on
+pub fn set_theme(theme: Theme) {
+    critical_section::with(|cs| THEME.borrow(cs).set(theme));
+}
@@ -0,0 +1,114 @@
+//! On/off switch
+
+use alloc::boxed::Box;
+
+use embedded_graphics::{
+    prelude::*,
+    primitives::{Circle, PrimitiveStyle, Rectangle, RoundedRectangle},
+};
+
+use crate::axs5106l::TouchEvent;
+
+use super::{Canvas, DrawError, Widget, WidgetBase, WidgetEvent, theme};
+
+/// Switch that flips its state when tapped
+pub struct Toggle {
+    base: WidgetBase,
+    on: bool,
+    on_change: Option<Box<dyn FnMut(bool)>>,
+}
+
+impl Toggle {
+    /// Create a switch, `bounds` should be about twice as wide as high
+    pub fn new(bounds: Rectangle, on: bool) -> Self {
+        Self {
+            base: WidgetBase::new(bounds),
+            on,
+            on_change: None,
+        }
+    }
+
+    /// Call `f` with the new state whenever the user flips the switch
+    pub fn on_change(mut self, f: impl FnMut(bool) + 'static) -> Self {
+        self.on_change = Some(Box::new(f));
+        self
+    }
+
+    /// Get the state
+    pub fn is_on(&self) -> bool {
+        self.on
+    }
+
+    /// Set the state without calling the callback
+    pub fn set_on(&mut self, on: bool) {
+        if self.on != on {
+            self.on = on;
+            self.base.invalidate();
+        }
+    }
+}
+
+impl Widget for Toggle {
+    fn base(&self) -> &WidgetBase {
+        &self.base
+    }
+
+    fn base_mut(&mut self) -> &mut WidgetBase {
+        &mut self.base
+    }
+
+    fn draw(&mut self, canvas: &mut Canvas<'_>) -> Result<(), DrawError> {
+        let theme = theme();
+        let bounds = self.base.bounds();
+        let height = bounds.size.height;
+        canvas.fill_solid(&bounds, theme.background)?;
+
+        let track = if self.on {
+            theme.active(&self.base, theme.accent)
+        } else {
+            theme.fill(&self.base)
+        };
+        RoundedRectangle::with_equal_corners(bounds, Size::new(height / 2, height / 2))
+            .into_styled(PrimitiveStyle::with_fill(track))
+            .draw(canvas)?;
+
+        // Knob on the right when on
+        let knob = height.saturating_sub(4);
+        let x = if self.on {
+            bounds.top_left.x + bounds.size.width.saturating_sub(knob) as i32 - 2
+        } else {
+            bounds.top_left.x + 2
+        };
+        Circle::new(Point::new(x, bounds.top_left.y + 2), knob)
+            .into_styled(PrimitiveStyle::with_fill(
+                theme.active(&self.base, theme.text),
+            ))
+            .draw(canvas)?;
+        Ok(())
+    }
+
+    fn touch(&mut self, event: &TouchEvent) -> Option<WidgetEvent> {
+        let (x, y) = event.position()?;
+        let inside = self.base.bounds().contains(Point::new(x as i32, y as i32));
+
+        match event {
+            TouchEvent::Down { .. } | TouchEvent::Move { .. } => {
+                self.base.set_pressed(inside);
+                None
+            }
+            TouchEvent::Up { .. } => {
+                let tapped = self.base.is_pressed() && inside;
+                self.base.set_pressed(false);
+                if !tapped {
+                    return None;
+                }
+                self.set_on(!self.on);
+                if let Some(f) = self.on_change.as_mut() {
+                    f(self.on);
+                }
+                Some(WidgetEvent::Toggled(self.on))
+            }
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,145 @@
+#!/usr/bin/env python3
+"""Convert a BDF bitmap font into a proportional `BitmapFont` for src/font.
+
+Usage: bdf2rust.py FONT.bdf NAME > src/font/name.rs
+
+Every glyph is trimmed to its ink and advances by its ink width plus one
+pixel of spacing, which turns the monospaced X11 misc-fixed fonts into
+proportional ones. Digits keep a common advance so changing numbers don't
+jitter. Only printable ASCII and the degree sign are kept, to save flash.
+"""
+
+import os
+import sys
+
+# Printable ASCII and the degree sign
+CHARS = [chr(c) for c in range(0x20, 0x7F)] + ["°"]
+# Pixels between two glyphs
+SPACING = 1
+
+
+def parse(path):
+    """Return (ascent, descent, {char: (rows, width, x, y)}) of a BDF file
+
+    rows are lists of 0/1 pixels, x and y the offset of the bottom left
+    corner from the pen position on the baseline, y up.
+    """
+    ascent = descent = None
+    glyphs = {}
+    with open(path) as f:
+        lines = iter(f.read().splitlines())
+    for line in lines:
+        key, _, value = line.partition(" ")
+        if key == "FONT_ASCENT":
+            ascent = int(value)
+        elif key == "FONT_DESCENT":
+            descent = int(value)
+        elif key == "STARTCHAR":
+            encoding = bbx = None
+            rows = []
+            for line in lines:
+                key, _, value = line.partition(" ")
+                if key == "ENCODING":
+                    encoding = int(value.split()[0])
+                elif key == "BBX":
+                    bbx = [int(v) for v in value.split()]
+                elif key == "BITMAP":
+                    for line in lines:
+                        if line == "ENDCHAR":
+                            break
+                        bits = int(line, 16)
+                        total = len(line) * 4
+                        rows.append(
+                            [(bits >> (total - 1 - i)) & 1 for i in range(bbx[0])]
+                        )
+                    break
+            if encoding is not None and encoding >= 0:
+                glyphs[chr(encoding)] = (rows, bbx[0], bbx[2], bbx[3])
+    return ascent, descent, glyphs
+
+
+def trim(rows, width, x, y):
+    """Cut empty rows and columns, return (rows, width, height, left, top)
+
+    left is the first ink column relative to the pen, top the first ink row
+    relative to the baseline, negative above it.
+    """
+    height = len(rows)
+    top_row = height - 1 + y  # row 0 in baseline coordinates, y up
+    used_rows = [r for r in range(height) if any(rows[r])]
+    used_cols = [c for c in range(width) if any(row[c] for row in rows)]
+    if not used_rows:
+        return [], 0, 0, 0, 0
+    r0, r1 = used_rows[0], used_rows[-1]
+    c0, c1 = used_cols[0], used_cols[-1]
+    rows = [row[c0 : c1 + 1] for row in rows[r0 : r1 + 1]]
+    return rows, c1 - c0 + 1, r1 - r0 + 1, x + c0, -(top_row - r0)
+
+
+def rust_char(c):
+    """Rust literal of the char c"""
+    if c in "'\\":
+        return f"'\\{c}'"
+    return f"'{c}'"
+
+
+def main():
+    path, name = sys.argv[1], sys.argv[2]
+    ascent, descent, source = parse(path)
+
+    glyphs = []
+    for c in CHARS:
+        if c not in source:
+            continue
+        rows, width, height, _, top = trim(*source[c])
+        glyphs.append([c, rows, width, height, top])
+
+    # Space is half as wide as the original cell
+    cell = source["0"][1]
+    digit_width = max(g[2] for g in glyphs if g[0].isdigit())
+
+    bits = []
+    entries = []
+    for c, rows, width, height, top in glyphs:
+        if c == " ":
+            advance, left = max(cell // 2, 2), 0
+        elif c.isdigit():
+            advance, left = digit_width + SPACING, (digit_width - width + 1) // 2
+        else:
+            advance, left = width + SPACING, 0
+        entries.append((c, advance, width, height, left, top, len(bits)))
+        for row in rows:
+            bits.extend(row)
+    bits.extend([0] * (-len(bits) % 8))
+    data = [
+        int("".join(str(b) for b in bits[i : i + 8]), 2) for i in range(0, len(bits), 8)
+    ]
+
+    source_name = os.path.basename(path)
+    print(f"//! Proportional version of the misc-fixed {source_name[:-4]} font")
+    print("//!")
+    print(f"//! Generated by `tools/bdf2rust.py {source_name} {name}`, do not edit.")
+    print("//! The misc-fixed fonts are in the public domain.")
+    print()
+    print("use super::{BitmapFont, Glyph};")
+    print()
+    print("#[rustfmt::skip]")
+    print(f"pub const {name}: BitmapFont = BitmapFont {{")
+    print(f"    ascent: {ascent},")
+    print(f"    descent: {descent},")
+    print("    glyphs: &[")
+    for c, advance, width, height, left, top, offset in entries:
+        print(
+            f"        Glyph::new({rust_char(c):>6}, {advance}, {width}, {height},"
+            f" {left}, {top}, {offset}),"
+        )
+    print("    ],")
+    print("    bitmap: &[")
+    for i in range(0, len(data), 16):
+        print("        " + " ".join(f"0x{b:02x}," for b in data[i : i + 16]))
+    print("    ],")
+    print("};")
+
+
+if __name__ == "__main__":
+    main()
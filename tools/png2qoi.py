@@ -0,0 +1,137 @@
+#!/usr/bin/env python3
+"""Convert a PNG file into a QOI image for src/qoi.rs.
+
+Usage: png2qoi.py IMAGE.png IMAGE.qoi
+
+Reads 8-bit grey, RGB, grey+alpha, RGBA and palette PNGs without
+interlacing, using only the Python standard library. Images with an alpha
+channel (or a palette with transparency) keep it, others are stored as RGB.
+"""
+
+import struct
+import sys
+import zlib
+
+# Channels per pixel by PNG color type
+CHANNELS = {0: 1, 2: 3, 3: 1, 4: 2, 6: 4}
+
+
+def paeth(a, b, c):
+    p = a + b - c
+    pa, pb, pc = abs(p - a), abs(p - b), abs(p - c)
+    if pa <= pb and pa <= pc:
+        return a
+    return b if pb <= pc else c
+
+
+def read_png(path):
+    """Return (width, height, has_alpha, [(r, g, b, a), ...]) of a PNG file"""
+    with open(path, "rb") as f:
+        data = f.read()
+    if data[:8] != b"\x89PNG\r\n\x1a\n":
+        sys.exit(f"{path}: not a PNG file")
+
+    pos = 8
+    idat = b""
+    palette = []
+    alphas = b""
+    while pos < len(data):
+        length, kind = struct.unpack(">I4s", data[pos : pos + 8])
+        chunk = data[pos + 8 : pos + 8 + length]
+        pos += 12 + length
+        if kind == b"IHDR":
+            width, height, depth, color, _, _, interlace = struct.unpack(">IIBBBBB", chunk)
+            if depth != 8 or interlace != 0 or color not in CHANNELS:
+                sys.exit(f"{path}: only 8-bit non-interlaced PNGs are supported")
+        elif kind == b"PLTE":
+            palette = [tuple(chunk[i : i + 3]) for i in range(0, len(chunk), 3)]
+        elif kind == b"tRNS":
+            alphas = chunk
+        elif kind == b"IDAT":
+            idat += chunk
+
+    # Undo the filter of every row
+    raw = zlib.decompress(idat)
+    bpp = CHANNELS[color]
+    stride = width * bpp
+    rows = []
+    previous = bytearray(stride)
+    for y in range(height):
+        start = y * (stride + 1)
+        kind, row = raw[start], bytearray(raw[start + 1 : start + 1 + stride])
+        for i in range(stride):
+            a = row[i - bpp] if i >= bpp else 0
+            b = previous[i]
+            c = previous[i - bpp] if i >= bpp else 0
+            row[i] = (row[i] + (0, a, b, (a + b) // 2, paeth(a, b, c))[kind]) & 0xFF
+        rows.append(row)
+        previous = row
+
+    pixels = []
+    for row in rows:
+        for x in range(width):
+            p = row[x * bpp : (x + 1) * bpp]
+            if color == 0:
+                pixels.append((p[0], p[0], p[0], 255))
+            elif color == 2:
+                pixels.append((p[0], p[1], p[2], 255))
+            elif color == 3:
+                alpha = alphas[p[0]] if p[0] < len(alphas) else 255
+                pixels.append(palette[p[0]] + (alpha,))
+            elif color == 4:
+                pixels.append((p[0], p[0], p[0], p[1]))
+            else:
+                pixels.append(tuple(p))
+    has_alpha = color in (4, 6) or bool(alphas)
+    return width, height, has_alpha, pixels
+
+
+def encode_qoi(width, height, has_alpha, pixels):
+    """Encode pixels as described on https://qoiformat.org"""
+    out = bytearray(b"qoif" + struct.pack(">IIBB", width, height, 4 if has_alpha else 3, 0))
+    index = [(0, 0, 0, 0)] * 64
+    previous = (0, 0, 0, 255)
+    run = 0
+    for i, pixel in enumerate(pixels):
+        if pixel == previous:
+            run += 1
+            if run == 62 or i == len(pixels) - 1:
+                out.append(0xC0 | (run - 1))
+                run = 0
+            continue
+        if run:
+            out.append(0xC0 | (run - 1))
+            run = 0
+
+        r, g, b, a = pixel
+        hash = (r * 3 + g * 5 + b * 7 + a * 11) % 64
+        if index[hash] == pixel:
+            out.append(hash)
+        elif a == previous[3]:
+            dr = (r - previous[0] + 128) % 256 - 128
+            dg = (g - previous[1] + 128) % 256 - 128
+            db = (b - previous[2] + 128) % 256 - 128
+            dr_dg, db_dg = dr - dg, db - dg
+            if -2 <= dr <= 1 and -2 <= dg <= 1 and -2 <= db <= 1:
+                out.append(0x40 | (dr + 2) << 4 | (dg + 2) << 2 | (db + 2))
+            elif -32 <= dg <= 31 and -8 <= dr_dg <= 7 and -8 <= db_dg <= 7:
+                out += bytes((0x80 | (dg + 32), (dr_dg + 8) << 4 | (db_dg + 8)))
+            else:
+                out += bytes((0xFE, r, g, b))
+        else:
+            out += bytes((0xFF, r, g, b, a))
+        index[hash] = pixel
+        previous = pixel
+    out += bytes(7) + b"\x01"
+    return out
+
+
+def main():
+    source, target = sys.argv[1], sys.argv[2]
+    width, height, has_alpha, pixels = read_png(source)
+    with open(target, "wb") as f:
+        f.write(encode_qoi(width, height, has_alpha, pixels))
+
+
+if __name__ == "__main__":
+    main()